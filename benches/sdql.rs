@@ -124,13 +124,13 @@ mod sdql {
             out
         }
 
-        fn merge(a: Self, b: Self) -> Self {
-            SdqlKind {
-                might_be_vector: a.might_be_vector || b.might_be_vector,
-                might_be_dict: a.might_be_dict || b.might_be_dict,
-                might_be_scalar: a.might_be_scalar || b.might_be_scalar,
-                might_be_bool: a.might_be_bool || b.might_be_bool,
-            }
+        fn merge(&mut self, other: Self) -> DidMerge {
+            let old = self.clone();
+            self.might_be_vector |= other.might_be_vector;
+            self.might_be_dict |= other.might_be_dict;
+            self.might_be_scalar |= other.might_be_scalar;
+            self.might_be_bool |= other.might_be_bool;
+            DidMerge(*self != old, *self != other)
         }
     }
 
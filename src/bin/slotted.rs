@@ -0,0 +1,297 @@
+//! A command-line driver for slotted e-graphs over [SymbolLang].
+//!
+//! ```text
+//! slotted <TERM_FILE> <RULE_FILE> [--iter-limit N] [--node-limit N] [--time-limit SECS] [--explain] [--dot FILE]
+//! slotted repl
+//! slotted script <SCRIPT_FILE>...
+//! ```
+//!
+//! The first form runs equality saturation on a term using a set of rewrite rules, both read from files.
+//! The term is written in the s-expression syntax of [SymbolLang], e.g. `(app (lam $x (var $x)) y)`.
+//! As in [SymbolLang], a slot that is directly followed by a child binds that slot within the child.
+//! Hence `(f $x y)` binds `$x` in `y`, while `(f y $x)` and `(f $x)` use `$x` as a free slot.
+//!
+//! The rule file contains one rule per line, lines starting with `#` are comments.
+//! Every rule starts with a name, which can't contain whitespace, parentheses or `:`:
+//! ```text
+//! beta: (app (lam $x ?b) ?t) => ?b[(var $x) := ?t]
+//! add-comm: (add ?a ?b) <=> (add ?b ?a)
+//! ```
+//! A rule written with `<=>` is added in both directions.
+//! `--dot FILE` writes the part of the e-graph reachable from the term in the Graphviz DOT format.
+//!
+//! The `repl` and `script` modes execute [Command]s on a single e-graph, either interactively or from script files.
+//! A script stops at the first failing command, e.g. a `check-eq` that doesn't hold.
+
+use slotted_egraphs::*;
+use std::io::Write;
+use std::process::exit;
+use std::time::Duration;
+
+const USAGE: &str = "usage: slotted <TERM_FILE> <RULE_FILE> [--iter-limit N] [--node-limit N] [--time-limit SECS] [--explain] [--dot FILE]
+       slotted repl
+       slotted script <SCRIPT_FILE>...";
+
+struct Args {
+    term_file: String,
+    rule_file: String,
+    iter_limit: Option<usize>,
+    node_limit: Option<usize>,
+    time_limit: Option<u64>,
+    explain: bool,
+    dot: Option<String>,
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+    match args.peek().map(|x| x.as_str()) {
+        Some("repl") => repl(),
+        Some("script") => {
+            args.next();
+            scripts(args)
+        }
+        _ => run(args),
+    }
+}
+
+fn repl() {
+    let mut interp = Interpreter::<SymbolLang>::new();
+    let mut buf = String::new();
+    let stdin = std::io::stdin();
+    loop {
+        print!("{}", if buf.is_empty() { "> " } else { ". " });
+        std::io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        buf.push_str(&line);
+        if !is_balanced(&buf) {
+            continue;
+        }
+
+        match Command::parse_all(&buf) {
+            Ok(cmds) => {
+                for cmd in cmds {
+                    match interp.execute(cmd) {
+                        Ok(o) if o.is_empty() => {}
+                        Ok(o) => println!("{o}"),
+                        Err(e) => println!("error: {e:?}"),
+                    }
+                }
+            }
+            Err(e) => println!("error: {e:?}"),
+        }
+        buf.clear();
+    }
+}
+
+// whether all parens and brackets are closed, ignoring comments.
+fn is_balanced(s: &str) -> bool {
+    let mut depth = 0;
+    for l in s.lines() {
+        for c in l.split(';').next().unwrap().chars() {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    depth <= 0
+}
+
+fn scripts(files: impl Iterator<Item = String>) {
+    let mut interp = Interpreter::<SymbolLang>::new();
+    for f in files {
+        let cmds =
+            Command::parse_all(&read_file(&f)).unwrap_or_else(|e| fail(&format!("{f}: {e:?}")));
+        for cmd in cmds {
+            match interp.execute(cmd) {
+                Ok(o) if o.is_empty() => {}
+                Ok(o) => println!("{o}"),
+                Err(e) => fail(&format!("{f}: {e:?}")),
+            }
+        }
+    }
+}
+
+fn run(args: impl Iterator<Item = String>) {
+    let args = parse_args(args).unwrap_or_else(|e| fail(&e));
+
+    let term_src = read_file(&args.term_file);
+    let term = RecExpr::<SymbolLang>::parse(&strip_comments(&term_src))
+        .unwrap_or_else(|e| fail(&format!("{}: can't parse term: {e:?}", args.term_file)));
+
+    let rule_src = read_file(&args.rule_file);
+    let rules =
+        parse_rules(&rule_src).unwrap_or_else(|e| fail(&format!("{}: {e}", args.rule_file)));
+
+    // only record explanations if they are asked for.
+    let egraph = EGraph::new().with_explanations(args.explain && cfg!(feature = "explanations"));
+    let mut runner = Runner::<SymbolLang, (), ()>::new()
+        .with_egraph(egraph)
+        .with_expr(&term);
+    if let Some(n) = args.iter_limit {
+        runner = runner.with_iter_limit(n);
+    }
+    if let Some(n) = args.node_limit {
+        runner = runner.with_node_limit(n);
+    }
+    if let Some(secs) = args.time_limit {
+        runner = runner.with_time_limit(Duration::from_secs(secs));
+    }
+    let report = runner.run(&rules);
+
+    let root = runner.egraph.find_applied_id(&runner.roots[0]);
+    let extractor = Extractor::new(&runner.egraph, AstSize);
+    let best = extractor
+        .try_extract(&root, &runner.egraph)
+        .unwrap_or_else(|e| fail(&format!("can't extract a term: {e:?}")));
+    let cost = extractor
+        .try_best_cost(&root, &runner.egraph)
+        .unwrap_or_else(|| fail("can't extract a term"));
+
+    println!("best: {best}");
+    println!("cost: {cost:?}");
+    println!("{report:#?}");
+
+    if let Some(f) = &args.dot {
+        let opts = DotOptions::default().with_roots([root.id]);
+        std::fs::write(f, runner.egraph.to_dot(&opts))
+            .unwrap_or_else(|e| fail(&format!("can't write {f}: {e}")));
+    }
+
+    if args.explain {
+        let root = runner.roots[0].clone();
+        explain(&extractor, &root, &mut runner.egraph);
+    }
+}
+
+#[cfg(feature = "explanations")]
+fn explain(
+    extractor: &Extractor<SymbolLang, AstSize>,
+    root: &AppliedId,
+    eg: &mut EGraph<SymbolLang>,
+) {
+    let res = extractor.extract_proven(root, eg);
+    println!("explanation:\n{}", res.proof.to_flat_string(eg));
+}
+
+#[cfg(not(feature = "explanations"))]
+fn explain(
+    _extractor: &Extractor<SymbolLang, AstSize>,
+    _root: &AppliedId,
+    _eg: &mut EGraph<SymbolLang>,
+) {
+    fail("--explain requires building with `--features explanations`");
+}
+
+fn parse_args(mut it: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut files = Vec::new();
+    let mut args = Args {
+        term_file: String::new(),
+        rule_file: String::new(),
+        iter_limit: None,
+        node_limit: None,
+        time_limit: None,
+        explain: false,
+        dot: None,
+    };
+
+    while let Some(a) = it.next() {
+        match &*a {
+            "--iter-limit" => args.iter_limit = Some(parse_num(&a, it.next())?),
+            "--node-limit" => args.node_limit = Some(parse_num(&a, it.next())?),
+            "--time-limit" => args.time_limit = Some(parse_num(&a, it.next())?),
+            "--explain" => args.explain = true,
+            "--dot" => args.dot = Some(it.next().ok_or("`--dot` expects a file name")?),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                exit(0);
+            }
+            _ if a.starts_with("--") => return Err(format!("unknown option `{a}`\n{USAGE}")),
+            _ => files.push(a),
+        }
+    }
+
+    let [term_file, rule_file] = <[String; 2]>::try_from(files).map_err(|_| USAGE.to_string())?;
+    args.term_file = term_file;
+    args.rule_file = rule_file;
+    Ok(args)
+}
+
+fn parse_num<T: std::str::FromStr>(opt: &str, v: Option<String>) -> Result<T, String> {
+    let v = v.ok_or_else(|| format!("`{opt}` expects a value"))?;
+    v.parse()
+        .map_err(|_| format!("`{opt}` expects a number, got `{v}`"))
+}
+
+// Rules are given as `name: lhs => rhs` or `name: lhs <=> rhs`.
+fn parse_rules(s: &str) -> Result<Vec<Rewrite<SymbolLang>>, String> {
+    let mut rules = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |msg: &str| format!("line {}: {msg}", i + 1);
+
+        let (name, rule) = split_name(line).ok_or_else(|| err("expected `name: lhs => rhs`"))?;
+        let (lhs, rhs, bidirectional) = if let Some((l, r)) = rule.split_once("<=>") {
+            (l.trim(), r.trim(), true)
+        } else if let Some((l, r)) = rule.split_once("=>") {
+            (l.trim(), r.trim(), false)
+        } else {
+            return Err(err("expected `=>` or `<=>`"));
+        };
+
+        let [l, r] = [lhs, rhs].map(|pat| {
+            Pattern::<SymbolLang>::parse(pat)
+                .map_err(|e| err(&format!("can't parse pattern `{pat}`: {e:?}")))
+        });
+        let (l, r) = (l?, r?);
+        check_rule(&l, &r).map_err(|e| err(&e))?;
+        if bidirectional {
+            check_rule(&r, &l).map_err(|e| err(&format!("in the reverse direction, {e}")))?;
+        }
+
+        rules.push(Rewrite::new(name, lhs, rhs));
+        if bidirectional {
+            rules.push(Rewrite::new(&format!("{name}-rev"), rhs, lhs));
+        }
+    }
+    Ok(rules)
+}
+
+// Splits `name: rule` at the colon behind the name.
+// The name has to be a single token, so that e.g. the `:=` in a nameless rule isn't taken for it.
+fn split_name(line: &str) -> Option<(&str, &str)> {
+    let (name, rule) = line.split_once(':')?;
+    let name = name.trim();
+    let is_token = !name.is_empty()
+        && !name
+            .chars()
+            .any(|c| c.is_whitespace() || "()[]$?".contains(c));
+    if !is_token || rule.starts_with('=') {
+        return None;
+    }
+    Some((name, rule))
+}
+
+fn strip_comments(s: &str) -> String {
+    s.lines()
+        .filter(|l| !l.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn read_file(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|e| fail(&format!("can't read {path}: {e}")))
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{msg}");
+    exit(1)
+}
@@ -0,0 +1,324 @@
+use crate::*;
+
+/// A command of the egglog-style command language understood by the [Interpreter].
+///
+/// The concrete syntax of each command is given next to its variant.
+/// Comments start with `;` and go until the end of the line.
+#[derive(Debug)]
+pub enum Command<L: Language> {
+    /// `(add term)`
+    Add(RecExpr<L>),
+
+    /// `(union term term)`
+    Union(RecExpr<L>, RecExpr<L>),
+
+    /// `(rule name lhs rhs)`
+    Rule(String, Pattern<L>, Pattern<L>),
+
+    /// `(run N rule...)`: Runs the given rules (or all rules, if none are given) for at most N iterations.
+    Run(usize, Vec<String>),
+
+    /// `(extract term)`
+    Extract(RecExpr<L>),
+
+    /// `(check-eq term term)`: Fails if the terms are not equal.
+    CheckEq(RecExpr<L>, RecExpr<L>),
+
+    /// `(explain term term)`
+    Explain(RecExpr<L>, RecExpr<L>),
+
+    /// `(dump)`: Shows the contents of the e-graph.
+    Dump,
+}
+
+#[derive(Debug)]
+pub enum CommandError {
+    Parse(ParseError),
+    UnbalancedParens,
+    UnknownCommand(String),
+    WrongArguments(String),
+    UnknownRule(String),
+    CheckFailed(String),
+    Unsupported(String),
+}
+
+impl From<ParseError> for CommandError {
+    fn from(e: ParseError) -> Self {
+        CommandError::Parse(e)
+    }
+}
+
+impl<L: Language> Command<L> {
+    /// Parses a sequence of commands, as found in a script file.
+    pub fn parse_all(s: &str) -> Result<Vec<Self>, CommandError> {
+        let s: String = s
+            .lines()
+            .map(|l| l.split(';').next().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let tok = tokenize(&s)?;
+
+        let mut out = Vec::new();
+        let mut rest = &tok[..];
+        while !rest.is_empty() {
+            let n = command_len(rest)?;
+            out.push(parse_command(&rest[..n])?);
+            rest = &rest[n..];
+        }
+        Ok(out)
+    }
+}
+
+// returns the number of tokens of the first command in `tok`.
+fn command_len(tok: &[Token]) -> Result<usize, CommandError> {
+    let Token::LParen = tok[0] else {
+        return Err(CommandError::Parse(ParseError::ParseState(tok.to_vec())));
+    };
+
+    let mut depth = 0;
+    for (i, t) in tok.iter().enumerate() {
+        match t {
+            Token::LParen | Token::LBracket => depth += 1,
+            Token::RParen | Token::RBracket => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 {
+            break;
+        }
+        if depth == 0 {
+            return Ok(i + 1);
+        }
+    }
+    Err(CommandError::UnbalancedParens)
+}
+
+// `tok` contains exactly one command, including its outer parens.
+fn parse_command<L: Language>(tok: &[Token]) -> Result<Command<L>, CommandError> {
+    let Token::Ident(name) = &tok[1] else {
+        return Err(CommandError::Parse(ParseError::ParseState(tok.to_vec())));
+    };
+    let mut args = &tok[2..tok.len() - 1];
+
+    let cmd = match &**name {
+        "add" => Command::Add(next_term(&mut args)?),
+        "union" => Command::Union(next_term(&mut args)?, next_term(&mut args)?),
+        "rule" => {
+            let name = next_ident(&mut args)?;
+            let lhs = Pattern::parse_prefix(&mut args)?;
+            let rhs = Pattern::parse_prefix(&mut args)?;
+            check_rule(&lhs, &rhs).map_err(CommandError::WrongArguments)?;
+            Command::Rule(name, lhs, rhs)
+        }
+        "run" => {
+            let n = next_ident(&mut args)?;
+            let n = n.parse().map_err(|_| {
+                CommandError::WrongArguments(format!("expected a number, got `{n}`"))
+            })?;
+            let mut rules = Vec::new();
+            while !args.is_empty() {
+                rules.push(next_ident(&mut args)?);
+            }
+            Command::Run(n, rules)
+        }
+        "extract" => Command::Extract(next_term(&mut args)?),
+        "check-eq" => Command::CheckEq(next_term(&mut args)?, next_term(&mut args)?),
+        "explain" => Command::Explain(next_term(&mut args)?, next_term(&mut args)?),
+        "dump" => Command::Dump,
+        _ => return Err(CommandError::UnknownCommand(name.clone())),
+    };
+
+    if !args.is_empty() {
+        return Err(CommandError::WrongArguments(format!(
+            "too many arguments for `{name}`"
+        )));
+    }
+
+    Ok(cmd)
+}
+
+fn next_ident(tok: &mut &[Token]) -> Result<String, CommandError> {
+    let Some(Token::Ident(x)) = tok.first() else {
+        return Err(CommandError::WrongArguments(format!(
+            "expected an identifier, got {:?}",
+            tok.first()
+        )));
+    };
+    *tok = &tok[1..];
+    Ok(x.clone())
+}
+
+fn next_term<L: Language>(tok: &mut &[Token]) -> Result<RecExpr<L>, CommandError> {
+    let pat = Pattern::parse_prefix(tok)?;
+    pattern_to_term(&pat).ok_or_else(|| {
+        CommandError::WrongArguments(format!("expected a term, got the pattern `{pat}`"))
+    })
+}
+
+fn pattern_to_term<L: Language>(pat: &Pattern<L>) -> Option<RecExpr<L>> {
+    let Pattern::ENode(n, children) = pat else {
+        return None;
+    };
+    let children = children
+        .iter()
+        .map(pattern_to_term)
+        .collect::<Option<Vec<_>>>()?;
+    Some(RecExpr {
+        node: n.clone(),
+        children,
+    })
+}
+
+impl<L: Language> Pattern<L> {
+    // parses a pattern from the beginning of `tok`, and advances `tok` behind it.
+    fn parse_prefix(tok: &mut &[Token]) -> Result<Self, CommandError> {
+        if tok.is_empty() {
+            return Err(CommandError::WrongArguments("missing argument".to_string()));
+        }
+        let (pat, rest) = parse_pattern(tok)?;
+        *tok = rest;
+        Ok(pat)
+    }
+}
+
+/// Executes [Command]s on a single [EGraph].
+///
+/// This is what drives the `slotted repl` and `slotted script` modes of the command-line driver.
+pub struct Interpreter<L: Language, N: Analysis<L> = ()> {
+    pub egraph: EGraph<L, N>,
+    rules: Vec<(String, Rewrite<L, N>)>,
+}
+
+impl<L: Language + 'static, N: Analysis<L> + 'static> Interpreter<L, N> {
+    pub fn new() -> Self {
+        Self::with_egraph(EGraph::new())
+    }
+
+    pub fn with_egraph(egraph: EGraph<L, N>) -> Self {
+        Interpreter {
+            egraph,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Parses and executes all commands in `s`, and returns their joined output.
+    ///
+    /// Stops at the first command that fails.
+    pub fn run_script(&mut self, s: &str) -> Result<String, CommandError> {
+        let mut out = String::new();
+        for cmd in Command::parse_all(s)? {
+            let o = self.execute(cmd)?;
+            if !o.is_empty() {
+                out.push_str(&o);
+                out.push('\n');
+            }
+        }
+        Ok(out)
+    }
+
+    /// Executes a single command, and returns its output.
+    pub fn execute(&mut self, cmd: Command<L>) -> Result<String, CommandError> {
+        match cmd {
+            Command::Add(t) => {
+                let i = self.egraph.add_syn_expr(t);
+                Ok(format!("{i:?}"))
+            }
+            Command::Union(a, b) => {
+                let a = self.egraph.add_syn_expr(a);
+                let b = self.egraph.add_syn_expr(b);
+                let changed = self
+                    .egraph
+                    .union_justified(&a, &b, Some(String::from("union")));
+                Ok(changed.to_string())
+            }
+            Command::Rule(name, lhs, rhs) => {
+                // a `Command` can also be built by hand, so the rule is checked again.
+                check_rule(&lhs, &rhs).map_err(CommandError::WrongArguments)?;
+                let rw = Rewrite::from_patterns_if(&name, lhs, rhs, |_, _| true);
+                self.rules.retain(|(n, _)| *n != name);
+                self.rules.push((name, rw));
+                Ok(String::new())
+            }
+            Command::Run(n, names) => self.run(n, &names),
+            Command::Extract(t) => {
+                let i = self.egraph.add_syn_expr(t);
+                Ok(ast_size_extract(&i, &self.egraph).to_string())
+            }
+            Command::CheckEq(a, b) => {
+                let (sa, sb) = (a.to_string(), b.to_string());
+                let a = self.egraph.add_syn_expr(a);
+                let b = self.egraph.add_syn_expr(b);
+                if self.egraph.eq(&a, &b) {
+                    Ok(String::new())
+                } else {
+                    Err(CommandError::CheckFailed(format!("{sa} != {sb}")))
+                }
+            }
+            Command::Explain(a, b) => self.explain(a, b),
+            Command::Dump => {
+                let mut out = String::new();
+                self.egraph.write_dump(&mut out, &|_| None).unwrap();
+                Ok(out.trim_end().to_string())
+            }
+        }
+    }
+
+    fn run(&mut self, n: usize, names: &[String]) -> Result<String, CommandError> {
+        for name in names {
+            if !self.rules.iter().any(|(n, _)| n == name) {
+                return Err(CommandError::UnknownRule(name.clone()));
+            }
+        }
+
+        let rws = self
+            .rules
+            .iter()
+            .filter(|(n, _)| names.is_empty() || names.contains(n))
+            .map(|(_, rw)| rw);
+
+        let mut iterations = 0;
+        let mut saturated = false;
+        while iterations < n {
+            iterations += 1;
+            if !apply_rewrites_iter(&mut self.egraph, rws.clone()) {
+                saturated = true;
+                break;
+            }
+        }
+
+        let mut out = format!("ran {iterations} iterations");
+        if saturated {
+            out.push_str(", saturated");
+        }
+        Ok(out)
+    }
+
+    #[cfg(feature = "explanations")]
+    fn explain(&mut self, a: RecExpr<L>, b: RecExpr<L>) -> Result<String, CommandError> {
+        if !self.egraph.explanations_enabled() {
+            return Err(CommandError::Unsupported(String::from(
+                "`explain` requires an e-graph with explanations enabled",
+            )));
+        }
+        let (sa, sb) = (a.to_string(), b.to_string());
+        let i = self.egraph.add_syn_expr(a.clone());
+        let j = self.egraph.add_syn_expr(b.clone());
+        if !self.egraph.eq(&i, &j) {
+            return Err(CommandError::CheckFailed(format!("{sa} != {sb}")));
+        }
+        let prf = self.egraph.explain_equivalence(a, b);
+        Ok(prf.to_flat_string(&self.egraph))
+    }
+
+    #[cfg(not(feature = "explanations"))]
+    fn explain(&mut self, _a: RecExpr<L>, _b: RecExpr<L>) -> Result<String, CommandError> {
+        Err(CommandError::Unsupported(String::from(
+            "`explain` requires the `explanations` feature",
+        )))
+    }
+}
+
+impl<L: Language + 'static, N: Analysis<L> + 'static> Default for Interpreter<L, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
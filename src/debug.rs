@@ -34,8 +34,9 @@ impl Debug for AppliedId {
     }
 }
 
+// Analysis data isn't required to implement Debug, so it's only shown by `EGraph::dump_to`.
 impl<L: Language, N: Analysis<L>> Debug for EGraph<L, N> {
-    fn fmt(&self, _f: &mut Formatter<'_>) -> Result {
-        todo!()
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.write_dump(f, &|_| None)
     }
 }
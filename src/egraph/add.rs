@@ -25,6 +25,10 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
         #[cfg(feature = "explanations")]
         {
+            if !self.explanations_enabled() {
+                return self.add(enode);
+            }
+
             let enode = self.synify_enode(enode);
 
             self.add(enode.clone());
@@ -194,8 +198,9 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         }
         for ref_id in sh.ids() {
             let usages = &mut self.classes.get_mut(&ref_id).unwrap().usages;
-            usages.insert(sh.clone());
+            usages.insert(sh.clone(), id);
         }
+        self.mark_changed(id);
     }
 
     #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
@@ -210,6 +215,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             let usages = &mut self.classes.get_mut(&ref_id).unwrap().usages;
             usages.remove(&sh);
         }
+        self.mark_changed(id);
 
         opt_psn.unwrap()
     }
@@ -230,20 +236,22 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         let c_id = Id(self.unionfind_len()); // Pick the next unused Id.
 
         let syn_slots = syn_enode.slots();
-        let proven_perm =
-            ProvenPerm::identity(c_id, &slots, &syn_slots, self.proof_registry.clone());
+        let proven_perm = ProvenPerm::identity(c_id, &slots, &syn_slots, &self.proof_registry);
 
         let c = EClass {
             nodes: HashMap::default(),
             group: Group::identity(&proven_perm),
             slots: slots.clone(),
-            usages: HashSet::default(),
-            syn_enode: syn_enode.clone(),
-            analysis_data: N::make(&self, &syn_enode),
+            usages: HashMap::default(),
+            syn_slots,
+            syn_enode: self.explanations_enabled().then(|| syn_enode.clone()),
+            analysis_data: Some(N::make(&self, &syn_enode)),
+            changed_at: 0,
         };
         self.classes.insert(c_id, c);
+        self.mark_changed(c_id);
 
-        {
+        if self.explanations_enabled() {
             // add syn_enode to the hashcons.
             let (sh, bij) = syn_enode.weak_shape();
 
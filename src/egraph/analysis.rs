@@ -1,12 +1,113 @@
 use crate::*;
+use std::ops::BitOr;
 
 /// E-Graph Analysis allows you to propagate information upwards through the E-Graph.
-pub trait Analysis<L: Language>: Eq + Clone {
+pub trait Analysis<L: Language>: Clone {
     fn make(eg: &EGraph<L, Self>, enode: &L) -> Self;
-    fn merge(l: Self, r: Self) -> Self;
+
+    /// Merges `other` into `self`, and reports which of both sides changed, see [DidMerge].
+    ///
+    /// The e-graph only re-analyses the parents of an e-class if its data changed.
+    fn merge(&mut self, other: Self) -> DidMerge;
+
+    /// Like [Analysis::merge], but reports when `self` and `other` contradict each other, e.g. when they are different constants.
+    /// In that case, `self` should be left unchanged.
+    /// A tuple of [AnalysisPart]s only leaves the conflicting components unchanged, and merges the others.
+    ///
+    /// The e-graph then keeps the old data of the e-class and records an [AnalysisConflict], instead of crashing.
+    /// This is usually caused by an unsound rewrite rule.
+    fn try_merge(&mut self, other: Self) -> Result<DidMerge, String> {
+        Ok(self.merge(other))
+    }
+
+    // Like `try_merge`, but also reports whether `self` changed despite a conflict, which only happens for tuples.
+    // Then the e-graph still has to re-analyse the parents of the e-class.
+    #[doc(hidden)]
+    fn try_merge_partial(&mut self, other: Self) -> (DidMerge, Option<String>) {
+        match self.try_merge(other) {
+            Ok(d) => (d, None),
+            Err(reason) => (DidMerge(false, false), Some(reason)),
+        }
+    }
+}
+
+/// The result of [Analysis::merge].
+///
+/// `DidMerge(a, b)` means that the merged data differs from the old `self` (if `a`), or from `other` (if `b`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DidMerge(pub bool, pub bool);
+
+impl BitOr for DidMerge {
+    type Output = DidMerge;
+
+    fn bitor(self, other: DidMerge) -> DidMerge {
+        DidMerge(self.0 || other.0, self.1 || other.1)
+    }
 }
 
 impl<L: Language> Analysis<L> for () {
     fn make(_eg: &EGraph<L, Self>, _: &L) {}
-    fn merge(_l: (), _r: ()) -> () {}
+    fn merge(&mut self, _other: ()) -> DidMerge {
+        DidMerge(false, false)
+    }
+}
+
+/// The analysis data of two equal e-classes contradict each other, see [Analysis::try_merge].
+#[derive(Debug, Clone)]
+pub struct AnalysisConflict {
+    /// The e-class whose data was merged into `r`.
+    /// If an e-node contradicts the data of its own e-class, this is the e-class that the e-node was originally added to.
+    pub l: Id,
+    pub r: Id,
+
+    /// The reason returned by [Analysis::try_merge].
+    pub reason: String,
+
+    /// A proof of `l = r`, talking about the syntactic terms of `l` and `r`.
+    /// It's `None` if the e-graph doesn't record explanations.
+    #[cfg(feature = "explanations")]
+    pub proof: Option<ProvenEq>,
+}
+
+impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// The conflicts found while merging analysis data, in the order they were found.
+    pub fn analysis_conflicts(&self) -> &[AnalysisConflict] {
+        &self.analysis_conflicts
+    }
+
+    /// Returns and forgets the conflicts found so far.
+    pub fn take_analysis_conflicts(&mut self) -> Vec<AnalysisConflict> {
+        std::mem::take(&mut self.analysis_conflicts)
+    }
+
+    /// Re-runs [Analysis::make] on the e-nodes of the e-classes `ids`, and propagates their data to their parents.
+    ///
+    /// Call this after changing analysis data through [EGraph::analysis_data_mut], which doesn't propagate anything by itself.
+    pub fn reanalyze(&mut self, ids: impl IntoIterator<Item = Id>) {
+        for i in ids {
+            let i = self.find_id(i);
+            for sh in self.classes[&i].nodes.keys() {
+                let v = self
+                    .pending
+                    .entry(sh.clone())
+                    .or_insert(PendingType::OnlyAnalysis);
+                *v = v.merge(PendingType::OnlyAnalysis);
+            }
+
+            // the data of `i` might have changed before, so its parents have to be re-analysed in any case.
+            self.touched_class(i, PendingType::OnlyAnalysis);
+        }
+        self.rebuild();
+    }
+
+    pub(crate) fn record_analysis_conflict(&mut self, conflict: AnalysisConflict) {
+        // a conflicting e-node is re-checked whenever its e-class changes, but it should only be reported once.
+        let known = self
+            .analysis_conflicts
+            .iter()
+            .any(|c| (c.l, c.r, &c.reason) == (conflict.l, conflict.r, &conflict.reason));
+        if !known {
+            self.analysis_conflicts.push(conflict);
+        }
+    }
 }
@@ -0,0 +1,148 @@
+use crate::*;
+use std::marker::PhantomData;
+
+/// An analysis that runs as a component of the analysis `N` of an e-graph, e.g. of a tuple.
+/// `I` is the location of the component in `N`, see [Has].
+///
+/// A tuple of [AnalysisPart]s is an [Analysis], which makes and merges each component separately.
+/// Unlike [Analysis::make], [AnalysisPart::make] sees the e-graph with the whole analysis `N`.
+/// So a component can read its own data, and the data of the other components, with [EGraph::analysis_part].
+///
+/// A component that only reads its own data can be implemented for every `N: Has<Self, I>`, which makes it usable in any tuple.
+/// A component that reads other components is usually implemented for one particular `N`.
+///
+/// An [Analysis] is not an [AnalysisPart] by itself, as [Analysis::make] needs an `EGraph<L, Self>`,
+/// while a component only gets the e-graph with the whole analysis `N`, which can't be turned into the former.
+/// So an analysis that should also work inside a tuple has to be written as an [AnalysisPart] that reads its own data with [EGraph::analysis_part].
+pub trait AnalysisPart<L: Language, N, I>: Clone {
+    fn make(eg: &EGraph<L, N>, enode: &L) -> Self
+    where
+        N: Analysis<L>;
+
+    /// See [Analysis::merge].
+    fn merge(&mut self, other: Self) -> DidMerge;
+
+    /// See [Analysis::try_merge]. On a conflict, `self` has to be left unchanged,
+    /// as the other components of the tuple are merged anyway.
+    fn try_merge(&mut self, other: Self) -> Result<DidMerge, String> {
+        Ok(self.merge(other))
+    }
+}
+
+/// Gives access to the component `T` of an analysis, see [EGraph::analysis_part].
+///
+/// `I` says where `T` is located, and is inferred by the compiler.
+/// Hence `T` has to occur exactly once in the analysis.
+pub trait Has<T, I> {
+    fn part(&self) -> &T;
+    fn part_mut(&mut self) -> &mut T;
+}
+
+/// The location of `T` in `T` itself, see [Has].
+pub struct Here;
+
+/// The location of `T` in the `K`-th component of a tuple, if `T` is located at `I` in that component, see [Has].
+pub struct InPart<const K: usize, I>(PhantomData<I>);
+
+impl<T> Has<T, Here> for T {
+    fn part(&self) -> &T {
+        self
+    }
+
+    fn part_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+macro_rules! impl_analysis_tuple {
+    ($($k:tt $t:ident),*) => {
+        impl<L: Language, $($t),*> Analysis<L> for ($($t,)*)
+        where
+            $($t: AnalysisPart<L, Self, InPart<$k, Here>>),*
+        {
+            fn make(eg: &EGraph<L, Self>, enode: &L) -> Self {
+                ($(<$t as AnalysisPart<L, Self, InPart<$k, Here>>>::make(eg, enode),)*)
+            }
+
+            fn merge(&mut self, other: Self) -> DidMerge {
+                DidMerge(false, false)
+                    $(| <$t as AnalysisPart<L, Self, InPart<$k, Here>>>::merge(&mut self.$k, other.$k))*
+            }
+
+            fn try_merge(&mut self, other: Self) -> Result<DidMerge, String> {
+                match self.try_merge_partial(other) {
+                    (d, None) => Ok(d),
+                    (_, Some(reason)) => Err(reason),
+                }
+            }
+
+            // The components that don't conflict are merged, the others are left unchanged, see `AnalysisPart::try_merge`.
+            // So nothing has to be copied to undo a merge.
+            fn try_merge_partial(&mut self, other: Self) -> (DidMerge, Option<String>) {
+                let mut out = DidMerge(false, false);
+                let mut conflict = None;
+                $(
+                    match <$t as AnalysisPart<L, Self, InPart<$k, Here>>>::try_merge(&mut self.$k, other.$k) {
+                        Ok(d) => out = out | d,
+                        Err(reason) => {
+                            conflict.get_or_insert(reason);
+                        }
+                    }
+                )*
+                (out, conflict)
+            }
+        }
+    };
+}
+
+// `T` is located in the `k`-th component `t` of the tuple `all`.
+macro_rules! impl_has_tuple {
+    ([$($all:ident),*] $k:tt $t:ident) => {
+        impl<T, I, $($all),*> Has<T, InPart<$k, I>> for ($($all,)*)
+        where
+            $t: Has<T, I>,
+        {
+            fn part(&self) -> &T {
+                self.$k.part()
+            }
+
+            fn part_mut(&mut self) -> &mut T {
+                self.$k.part_mut()
+            }
+        }
+    };
+}
+
+impl_analysis_tuple!(0 A, 1 B);
+impl_analysis_tuple!(0 A, 1 B, 2 C);
+impl_analysis_tuple!(0 A, 1 B, 2 C, 3 D);
+
+impl_has_tuple!([A, B] 0 A);
+impl_has_tuple!([A, B] 1 B);
+impl_has_tuple!([A, B, C] 0 A);
+impl_has_tuple!([A, B, C] 1 B);
+impl_has_tuple!([A, B, C] 2 C);
+impl_has_tuple!([A, B, C, D] 0 A);
+impl_has_tuple!([A, B, C, D] 1 B);
+impl_has_tuple!([A, B, C, D] 2 C);
+impl_has_tuple!([A, B, C, D] 3 D);
+
+impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// The component `T` of the analysis data of the e-class `i`, see [Has].
+    ///
+    /// For example, with the analysis `(ConstProp, FreeVars)`, `eg.analysis_part::<ConstProp, _>(i)` returns the `ConstProp` data of `i`.
+    pub fn analysis_part<T, I>(&self, i: Id) -> &T
+    where
+        N: Has<T, I>,
+    {
+        self.analysis_data(i).part()
+    }
+
+    /// Like [EGraph::analysis_part], but mutable. See [EGraph::reanalyze] for propagating the changes.
+    pub fn analysis_part_mut<T, I>(&mut self, i: Id) -> &mut T
+    where
+        N: Has<T, I>,
+    {
+        self.analysis_data_mut(i).part_mut()
+    }
+}
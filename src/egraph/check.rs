@@ -64,7 +64,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         let mut usages = HashMap::default();
 
         for (i, _) in &self.classes {
-            usages.insert(*i, HashSet::default());
+            usages.insert(*i, HashMap::default());
         }
 
         // redundancy-check for leaders.
@@ -81,9 +81,9 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             assert_eq!(sem, sem2);
 
             #[cfg(feature = "explanations")]
-            {
+            if let Some(proof) = self.proven_unionfind_get(*i).proof {
                 let c = &self.classes[i];
-                let eq = self.proven_unionfind_get(*i).proof.equ();
+                let eq = proof.equ();
                 // eq.l.m :: slots(i) -> X
                 // eq.r.m :: slots(i) -> X
                 let tmp = eq.l.m.compose_partial(&eq.r.m.inverse());
@@ -99,12 +99,15 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
                 hashcons.insert(sh.clone(), *i);
 
                 for ref_id in sh.ids() {
-                    usages.get_mut(&ref_id).unwrap().insert(sh.clone());
+                    usages.get_mut(&ref_id).unwrap().insert(sh.clone(), *i);
                 }
             }
         }
 
         assert_eq!(hashcons, self.hashcons);
+        if !self.explanations_enabled() {
+            assert!(self.syn_hashcons.is_empty());
+        }
         for (i, c) in &self.classes {
             assert_eq!(usages[&i], c.usages);
         }
@@ -132,7 +135,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
                 assert_eq!(self.unionfind_get(i), self.mk_sem_identity_applied_id(i));
             } else {
                 assert!(self.classes[&i].nodes.is_empty());
-                for sh in &self.classes[&i].usages {
+                for sh in self.classes[&i].usages.keys() {
                     assert_eq!(self.pending.get(&sh), Some(&PendingType::Full));
                 }
             }
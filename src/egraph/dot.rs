@@ -0,0 +1,172 @@
+use crate::*;
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+/// Configures which part of an [EGraph] is exported by [EGraph::to_dot].
+#[derive(Clone, Debug, Default)]
+pub struct DotOptions {
+    /// Only export the classes reachable from these roots. If empty, all classes are exported.
+    pub roots: Vec<Id>,
+
+    /// Only export classes up to this distance from the roots.
+    pub max_depth: Option<usize>,
+}
+
+impl DotOptions {
+    pub fn with_roots(mut self, roots: impl IntoIterator<Item = Id>) -> Self {
+        self.roots = roots.into_iter().collect();
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+}
+
+impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// Exports the e-graph in the Graphviz DOT format.
+    ///
+    /// Each e-class is drawn as a cluster, labelled with its slots, its redundant slots and the generators of its symmetry group.
+    /// Each edge is labelled by the [SlotMap] that the e-node passes into the child e-class.
+    pub fn to_dot(&self, opts: &DotOptions) -> String {
+        let classes = self.dot_classes(opts);
+        let exported: HashSet<Id> = classes.iter().copied().collect();
+
+        let mut out = String::new();
+        writeln!(out, "digraph egraph {{").unwrap();
+        writeln!(out, "  compound=true").unwrap();
+        writeln!(out, "  clusterrank=local").unwrap();
+
+        let mut edges = String::new();
+        let mut truncated = HashSet::default();
+        for &i in &classes {
+            let c = &self.classes[&i];
+
+            let mut label = vec![format!("{i:?}({})", show_slots(&c.slots))];
+            let redundant = &c.syn_slots - &c.slots;
+            if !redundant.is_empty() {
+                label.push(format!("redundant: {}", show_slots(&redundant)));
+            }
+            let mut generators: Vec<Perm> =
+                c.group.generators().into_iter().map(|p| p.elem).collect();
+            generators.sort();
+            for g in generators {
+                label.push(format!("symmetry: {g:?}"));
+            }
+            let label: Vec<String> = label.iter().map(|x| escape(x)).collect();
+
+            writeln!(out, "  subgraph cluster_{} {{", i.0).unwrap();
+            writeln!(out, "    style=dotted").unwrap();
+            writeln!(out, "    label=\"{}\"", label.join("\\n")).unwrap();
+
+            let nodes = self.sorted_enodes(i);
+            if nodes.is_empty() {
+                writeln!(out, "    c{}_0 [shape=point]", i.0).unwrap();
+            }
+            for (j, n) in nodes.iter().enumerate() {
+                writeln!(
+                    out,
+                    "    c{}_{j} [label=\"{}\"]",
+                    i.0,
+                    escape(&show_enode(n))
+                )
+                .unwrap();
+
+                for (k, child) in n.applied_id_occurrences().into_iter().enumerate() {
+                    let target = if exported.contains(&child.id) {
+                        format!("c{}_0 [lhead=cluster_{}, ", child.id.0, child.id.0)
+                    } else {
+                        truncated.insert(child.id);
+                        format!("t{} [style=dashed, ", child.id.0)
+                    };
+                    let mut edge_label = k.to_string();
+                    if !child.m.is_empty() {
+                        write!(edge_label, ": {:?}", child.m).unwrap();
+                    }
+                    writeln!(
+                        edges,
+                        "  c{}_{j} -> {target}label=\"{}\"]",
+                        i.0,
+                        escape(&edge_label)
+                    )
+                    .unwrap();
+                }
+            }
+            writeln!(out, "  }}").unwrap();
+        }
+
+        let mut truncated: Vec<Id> = truncated.into_iter().collect();
+        truncated.sort();
+        for i in truncated {
+            writeln!(out, "  t{} [label=\"{i:?} ...\", shape=none]", i.0).unwrap();
+        }
+
+        out.push_str(&edges);
+        writeln!(out, "}}").unwrap();
+        out
+    }
+
+    // the classes to export, in ascending order.
+    fn dot_classes(&self, opts: &DotOptions) -> Vec<Id> {
+        if opts.roots.is_empty() && opts.max_depth.is_none() {
+            return self.ids();
+        }
+
+        let roots: Vec<Id> = if opts.roots.is_empty() {
+            self.ids()
+        } else {
+            opts.roots.iter().map(|i| self.find_id(*i)).collect()
+        };
+
+        let mut seen: HashSet<Id> = HashSet::default();
+        let mut queue: VecDeque<(Id, usize)> = VecDeque::new();
+        for r in roots {
+            if seen.insert(r) {
+                queue.push_back((r, 0));
+            }
+        }
+        while let Some((i, depth)) = queue.pop_front() {
+            if opts.max_depth.is_some_and(|d| depth >= d) {
+                continue;
+            }
+            for n in self.enodes(i) {
+                for child in n.ids() {
+                    if seen.insert(child) {
+                        queue.push_back((child, depth + 1));
+                    }
+                }
+            }
+        }
+
+        let mut out: Vec<Id> = seen.into_iter().collect();
+        out.sort();
+        out
+    }
+}
+
+fn show_slots(slots: &SmallHashSet<Slot>) -> String {
+    let mut v: Vec<Slot> = slots.iter().copied().collect();
+    v.sort();
+    v.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// e.g. `lam $f1 id3`.
+fn show_enode<L: Language>(n: &L) -> String {
+    n.to_syntax()
+        .into_iter()
+        .map(|x| match x {
+            SyntaxElem::String(s) => s,
+            SyntaxElem::AppliedId(a) => format!("{:?}", a.id),
+            SyntaxElem::Slot(s) => s.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
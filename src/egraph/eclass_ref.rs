@@ -0,0 +1,104 @@
+use crate::*;
+
+/// A read-only view of an e-class, see [EGraph::eclass].
+///
+/// Everything is borrowed from the [EGraph], so looking at an e-class doesn't copy its contents.
+pub struct EClassRef<'a, L: Language, N: Analysis<L>> {
+    eg: &'a EGraph<L, N>,
+    id: Id,
+    class: &'a EClass<L, N>,
+}
+
+impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// Returns a view of the e-class `i`.
+    ///
+    /// Panics if `i` is dead, see [EGraph::is_alive].
+    pub fn eclass(&self, i: Id) -> EClassRef<'_, L, N> {
+        assert!(self.is_alive(i), "Can't access dead class");
+
+        EClassRef {
+            eg: self,
+            id: i,
+            class: &self.classes[&i],
+        }
+    }
+}
+
+impl<'a, L: Language, N: Analysis<L>> EClassRef<'a, L, N> {
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// The slots of this e-class, see [EGraph::slots].
+    pub fn slots(&self) -> impl Iterator<Item = Slot> + 'a {
+        self.class.slots.iter().copied()
+    }
+
+    /// The e-node this e-class was created from. It still has all slots, including the redundant ones.
+    ///
+    /// Is `None` if explanations are disabled, see [EGraph::with_explanations].
+    pub fn syn_enode(&self) -> Option<&'a L> {
+        self.class.syn_enode.as_ref()
+    }
+
+    /// The slots of the e-node this e-class was created from that were found to be redundant, i.e. that this e-class doesn't depend on.
+    pub fn redundant_slots(&self) -> impl Iterator<Item = Slot> + 'a {
+        let slots = &self.class.slots;
+        self.class
+            .syn_slots
+            .iter()
+            .copied()
+            .filter(move |s| !slots.contains(s))
+    }
+
+    /// The generators of the symmetry group of this e-class, excluding the identity.
+    /// Each generator is a permutation of the slots of this e-class.
+    pub fn symmetry_generators(&self) -> impl Iterator<Item = &'a SlotMap> + 'a {
+        self.class.group.generators_iter().map(|p| &p.elem)
+    }
+
+    /// The number of permutations in the symmetry group of this e-class, including the identity.
+    pub fn symmetry_order(&self) -> usize {
+        self.class.group.count()
+    }
+
+    /// The slots that `s` can be permuted to by the symmetries of this e-class, including `s` itself.
+    ///
+    /// Panics if `s` is not a slot of this e-class.
+    pub fn orbit(&self, s: Slot) -> impl Iterator<Item = Slot> + 'a {
+        assert!(
+            self.class.slots.contains(&s),
+            "{s:?} is not a slot of the e-class"
+        );
+
+        // a search through the generators, which yields each slot when it's first reached.
+        let group = &self.class.group;
+        let mut seen: HashSet<Slot> = HashSet::default();
+        seen.insert(s);
+        let mut todo = vec![s];
+        std::iter::from_fn(move || {
+            let x = todo.pop()?;
+            for g in group.generators_iter() {
+                let y = g.elem[x];
+                if seen.insert(y) {
+                    todo.push(y);
+                }
+            }
+            Some(x)
+        })
+    }
+
+    /// The e-nodes that have this e-class as a child, together with the e-class containing them.
+    /// Each e-node is written as it exists in its e-class, see [EGraph::enodes].
+    pub fn usages(&self) -> impl Iterator<Item = (Id, L)> + 'a {
+        let eg = self.eg;
+        self.class.usages.iter().map(move |(x, &j)| {
+            let bij = &eg.classes[&j].nodes[x].elem;
+            (j, x.apply_slotmap(bij))
+        })
+    }
+
+    pub fn analysis_data(&self) -> &'a N {
+        self.class.analysis_data.as_ref().unwrap()
+    }
+}
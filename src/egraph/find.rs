@@ -22,9 +22,11 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     pub(crate) fn unionfind_set(&self, i: Id, pai: ProvenAppliedId) {
         #[cfg(feature = "explanations")]
         if CHECKS {
-            pai.proof.check(self);
-            assert_eq!(i, pai.proof.l.id);
-            assert_eq!(pai.elem.id, pai.proof.r.id);
+            if let Some(proof) = &pai.proof {
+                proof.check(self);
+                assert_eq!(i, proof.l.id);
+                assert_eq!(pai.elem.id, proof.r.id);
+            }
         }
 
         let mut lock = self.unionfind.borrow_mut();
@@ -62,6 +64,16 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         out.into_iter()
     }
 
+    // The proofs stored in the unionfind, without path compression.
+    #[cfg(feature = "explanations")]
+    pub(crate) fn unionfind_proofs(&self) -> Vec<ProvenEq> {
+        self.unionfind
+            .borrow()
+            .iter()
+            .filter_map(|pai| pai.proof.clone())
+            .collect()
+    }
+
     pub(crate) fn unionfind_len(&self) -> usize {
         self.unionfind.borrow().len()
     }
@@ -110,7 +122,11 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
         #[cfg(feature = "explanations")]
         {
-            pai2.proof = prove_transitivity(pai.proof.clone(), pai2.proof, &self.proof_registry);
+            pai2.proof = pai
+                .proof
+                .clone()
+                .zip(pai2.proof)
+                .map(|(x, y)| prove_transitivity(x, y, &self.proof_registry));
         }
 
         if CHECKS {
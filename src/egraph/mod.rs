@@ -17,6 +17,21 @@ pub use check::*;
 
 mod analysis;
 pub use analysis::*;
+
+mod analysis_part;
+pub use analysis_part::*;
+
+mod dot;
+pub use dot::*;
+
+mod eclass_ref;
+pub use eclass_ref::*;
+
+#[cfg(feature = "serialize")]
+mod serialize;
+#[cfg(feature = "serialize")]
+pub use serialize::*;
+
 use vec_collections::AbstractVecSet;
 
 use std::cell::RefCell;
@@ -46,7 +61,7 @@ pub struct EGraph<L: Language, N: Analysis<L> = ()> {
     hashcons: HashMap<L, Id>,
 
     // For each (syn_slotset applied) non-normalized (i.e. "syntactic") weak shape, find the e-class who has this as syn_enode.
-    // TODO remove this if explanations are disabled.
+    // Stays empty if explanations are disabled, as `add_syn` then behaves like `add`.
     syn_hashcons: HashMap<L, AppliedId>,
 
     // E-Nodes that need to be re-processed, stored as shapes.
@@ -56,6 +71,12 @@ pub struct EGraph<L: Language, N: Analysis<L> = ()> {
     pub(crate) proof_registry: ProofRegistry,
 
     pub(crate) subst_method: Option<Box<dyn SubstMethod<L, N>>>,
+
+    // Counts the changes to e-classes, see `EClass::changed_at`.
+    pub(crate) change_count: usize,
+
+    // see `EGraph::analysis_conflicts`.
+    analysis_conflicts: Vec<AnalysisConflict>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -77,16 +98,23 @@ pub(crate) struct EClass<L: Language, N: Analysis<L>> {
     // Should not contain Slot(0).
     slots: SmallHashSet<Slot>,
 
-    // Shows which Shapes refer to this EClass.
-    usages: HashSet<L>,
+    // Shows which Shapes refer to this EClass, and the EClass containing each of them.
+    usages: HashMap<L, Id>,
 
     // Expresses the self-symmetries of this e-class.
     pub(crate) group: Group<ProvenPerm>,
 
-    // TODO remove this if explanations are disabled.
-    syn_enode: L,
+    // The slots of the e-node this class was allocated with, including the redundant ones.
+    syn_slots: SmallHashSet<Slot>,
 
-    analysis_data: N,
+    // The e-node this class was allocated with. Only stored if explanations are enabled.
+    syn_enode: Option<L>,
+
+    // Is `None` once the e-class is dead, as its data was moved into the e-class it was unified with.
+    analysis_data: Option<N>,
+
+    // The `EGraph::change_count` at the last time that the nodes or slots of this e-class changed.
+    pub(crate) changed_at: usize,
 }
 
 impl<L: Language, N: Analysis<L>> EGraph<L, N> {
@@ -105,27 +133,83 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             pending: Default::default(),
             proof_registry: ProofRegistry::default(),
             subst_method: Some(S::new_boxed()),
+            change_count: 0,
+            analysis_conflicts: Vec::new(),
         }
     }
 
+    /// Chooses whether this e-graph records explanations.
+    ///
+    /// Explanations are enabled by default if the `explanations` feature is active.
+    /// Without them, no proofs are recorded and [EGraph::add_syn] behaves like [EGraph::add], which saves time and memory,
+    /// but the `explain_*` methods can't be used.
+    ///
+    /// Recording explanations still requires the `explanations` feature:
+    /// without it, this method has no effect and [EGraph::explanations_enabled] is always `false`.
+    ///
+    /// Panics if the e-graph is not empty.
+    pub fn with_explanations(
+        #[allow(unused_mut)] mut self,
+        #[allow(unused)] enabled: bool,
+    ) -> Self {
+        assert!(
+            self.classes.is_empty(),
+            "Can't change explanations of a non-empty e-graph"
+        );
+
+        #[cfg(feature = "explanations")]
+        {
+            self.proof_registry = match enabled {
+                true => ProofRegistry::default(),
+                false => ProofRegistry::disabled(),
+            };
+        }
+
+        self
+    }
+
+    /// Whether this e-graph records explanations, see [EGraph::with_explanations].
+    pub fn explanations_enabled(&self) -> bool {
+        #[cfg(feature = "explanations")]
+        {
+            self.proof_registry.is_enabled()
+        }
+
+        #[cfg(not(feature = "explanations"))]
+        {
+            false
+        }
+    }
+
+    // Records that the nodes or slots of the e-class `id` changed.
+    pub(crate) fn mark_changed(&mut self, id: Id) {
+        self.change_count += 1;
+        self.classes.get_mut(&id).unwrap().changed_at = self.change_count;
+    }
+
     pub fn slots(&self, id: Id) -> SmallHashSet<Slot> {
         self.classes[&id].slots.clone()
     }
 
     pub(crate) fn syn_slots(&self, id: Id) -> SmallHashSet<Slot> {
-        self.classes[&id].syn_enode.slots()
+        self.classes[&id].syn_slots.clone()
     }
 
     pub fn analysis_data(&self, i: Id) -> &N {
-        &self.classes[&self.find_id(i)].analysis_data
+        self.classes[&self.find_id(i)]
+            .analysis_data
+            .as_ref()
+            .unwrap()
     }
 
     pub fn analysis_data_mut(&mut self, i: Id) -> &mut N {
-        &mut self
-            .classes
-            .get_mut(&self.find_id(i))
+        let i = self.find_id(i);
+        self.classes
+            .get_mut(&i)
             .unwrap()
             .analysis_data
+            .as_mut()
+            .unwrap()
     }
 
     pub fn enodes(&self, i: Id) -> HashSet<L> {
@@ -139,6 +223,23 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             .collect()
     }
 
+    // The e-nodes of the class `i`, in a deterministic order.
+    // They are sorted by their form with the slot names of `canonical_names`, so the order doesn't depend on unrelated fresh slots.
+    pub(crate) fn sorted_enodes(&self, i: Id) -> Vec<L> {
+        let names = canonical_names(&self.classes[&i].slots);
+        let mut nodes: Vec<(String, L)> = self
+            .enodes(i)
+            .into_iter()
+            .map(|n| {
+                let c =
+                    self.canonical_enode(&n, &names, &|j| canonical_names(&self.classes[&j].slots));
+                (format!("{c:?}"), n)
+            })
+            .collect();
+        nodes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        nodes.into_iter().map(|(_, n)| n).collect()
+    }
+
     // Generates fresh slots for redundant slots.
     pub fn enodes_applied(&self, i: &AppliedId) -> HashSet<L> {
         let mut out = HashSet::default();
@@ -222,48 +323,137 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
     /// Prints the contents of the E-Graph. Helpful for debugging.
     pub fn dump(&self) {
-        println!("");
+        let mut s = String::new();
+        self.write_dump(&mut s, &|_| None).unwrap();
+        println!("\n{s}");
+    }
+
+    /// Writes the contents of the E-Graph, including the analysis data of each e-class.
+    /// The [Debug] output of the E-Graph is the same, but without the analysis data.
+    ///
+    /// E-classes are ordered by [Id], and e-nodes and symmetry generators within an e-class are sorted.
+    /// The slots of each e-class are printed as `$0`, `$1`, ..., numbered in the order of their original names.
+    /// Hence building an E-Graph by the same sequence of operations gives the same output, even if other fresh slots were created in between,
+    /// which makes it usable for snapshot tests.
+    /// E-Graphs that were built in a different order might still print differently, as their slots can be numbered differently.
+    pub fn dump_to(&self, mut w: impl std::io::Write) -> std::io::Result<()>
+    where
+        N: std::fmt::Debug,
+    {
+        let mut s = String::new();
+        self.write_dump(&mut s, &|n| Some(format!("{n:?}")))
+            .unwrap();
+        w.write_all(s.as_bytes())
+    }
+
+    // `analysis` optionally shows the analysis data of an e-class.
+    pub(crate) fn write_dump(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        analysis: &dyn Fn(&N) -> Option<String>,
+    ) -> std::fmt::Result {
         let mut v: Vec<(&Id, &EClass<L, N>)> = self.classes.iter().collect();
         v.sort_by_key(|(x, _)| *x);
 
+        let mut first = true;
         for (i, c) in v {
             if c.nodes.len() == 0 {
                 continue;
             }
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
 
-            let mut slot_order: Vec<Slot> = c.slots.iter().cloned().collect();
-            slot_order.sort();
-            let slot_str = slot_order
-                .iter()
-                .map(|x| x.to_string())
+            // fresh slot names depend on how many slots were created before, so they are replaced by their rank.
+            let names = canonical_names(&c.slots);
+            let class_names = |j: Id| canonical_names(&self.classes[&j].slots);
+            let slot_str = (0..names.len())
+                .map(|k| Slot::numeric(k as u32).to_string())
                 .collect::<Vec<_>>()
                 .join(", ");
-            println!("\n{:?}({}):", i, &slot_str);
+            writeln!(f, "{:?}({}):", i, &slot_str)?;
 
-            println!(">> {:?}", &c.syn_enode);
+            if let Some(syn) = &c.syn_enode {
+                let syn_names = |j: Id| canonical_names(&self.syn_slots(j));
+                let syn = self.canonical_enode(syn, &syn_names(*i), &syn_names);
+                writeln!(f, ">> {syn:?}")?;
+            }
 
-            for (sh, psn) in &c.nodes {
-                let n = sh.apply_slotmap(&psn.elem);
+            let mut nodes: Vec<String> = c
+                .nodes
+                .iter()
+                .map(|(sh, psn)| {
+                    let n = sh.apply_slotmap(&psn.elem);
+                    let n = self.canonical_enode(&n, &names, &class_names);
+
+                    #[cfg(feature = "explanations")]
+                    let s = format!(" - {n:?}    [originally {:?}]", psn.src_id);
+
+                    #[cfg(not(feature = "explanations"))]
+                    let s = format!(" - {n:?}");
+
+                    s
+                })
+                .collect();
+            nodes.sort();
+            for n in nodes {
+                writeln!(f, "{n}")?;
+            }
 
-                #[cfg(feature = "explanations")]
-                println!(" - {n:?}    [originally {:?}]", psn.src_id);
+            let mut generators: Vec<Perm> = c
+                .group
+                .generators()
+                .into_iter()
+                .map(|pp| pp.elem.iter().map(|(x, y)| (names[x], names[y])).collect())
+                .collect();
+            generators.sort();
+            for g in generators {
+                writeln!(f, " -- {g:?}")?;
+            }
 
-                #[cfg(not(feature = "explanations"))]
-                println!(" - {n:?}");
+            if let Some(a) = c.analysis_data.as_ref().and_then(analysis) {
+                writeln!(f, " analysis: {a}")?;
             }
-            for pp in &c.group.generators() {
-                println!(" -- {:?}", pp.elem);
+        }
+        Ok(())
+    }
+
+    // Renames the slots of `n` for printing. `names` renames the slots of its e-class.
+    // The other slots of `n`, e.g. bound or redundant ones, are numbered behind them in order of occurrence.
+    // The slots of each child e-class `j` are renamed by `child_names(j)`.
+    pub(crate) fn canonical_enode(
+        &self,
+        n: &L,
+        names: &SlotMap,
+        child_names: &dyn Fn(Id) -> SlotMap,
+    ) -> L {
+        let mut m = names.clone();
+        for s in n.all_slot_occurrences() {
+            if !m.contains_key(s) {
+                m.insert(s, Slot::numeric(m.len() as u32));
             }
         }
-        println!("");
+
+        let mut n = n.clone();
+        for x in n.applied_id_occurrences_mut() {
+            let keys = child_names(x.id);
+            x.m =
+                x.m.iter()
+                    .map(|(k, v)| (keys.get(k).unwrap_or(k), v))
+                    .collect();
+        }
+        for s in n.all_slot_occurrences_mut() {
+            *s = m[*s];
+        }
+        n
     }
 
     // The resulting e-nodes are written as they exist in the e-class.
     pub(crate) fn usages(&self, i: Id) -> Vec<L> {
         let mut out = Vec::new();
-        for x in &self.classes[&i].usages {
-            let j = self.lookup(x).unwrap().id;
-            let bij = &self.classes[&j].nodes[&x].elem;
+        for (x, j) in &self.classes[&i].usages {
+            let bij = &self.classes[j].nodes[x].elem;
             let x = x.apply_slotmap(bij);
             out.push(x);
         }
@@ -400,23 +590,39 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     /// Returns the canonical term corresponding to `i`.
     ///
     /// This function will use [EGraph::get_syn_node] repeatedly to build up this term.
-    pub fn get_syn_expr(&self, i: &AppliedId) -> RecExpr<L> {
-        let enode = self.get_syn_node(i);
+    /// Returns `None` if explanations are disabled, as the canonical terms are not stored then.
+    pub fn get_syn_expr(&self, i: &AppliedId) -> Option<RecExpr<L>> {
+        let enode = self.get_syn_node(i)?;
         let cs = enode
             .applied_id_occurrences()
             .iter()
             .map(|x| self.get_syn_expr(x))
-            .collect();
-        RecExpr {
+            .collect::<Option<_>>()?;
+        Some(RecExpr {
             node: nullify_app_ids(&enode),
             children: cs,
-        }
+        })
     }
 
     /// Returns the canonical e-node corresponding to `i`.
-    pub fn get_syn_node(&self, i: &AppliedId) -> L {
-        let syn = &self.classes[&i.id].syn_enode;
-        syn.apply_slotmap(&i.m)
+    ///
+    /// Returns `None` if explanations are disabled.
+    pub fn get_syn_node(&self, i: &AppliedId) -> Option<L> {
+        let syn = self.classes[&i.id].syn_enode.as_ref()?;
+        Some(syn.apply_slotmap(&i.m))
+    }
+
+    // The canonical e-node of an e-graph that records explanations.
+    pub(crate) fn syn_node(&self, i: &AppliedId) -> L {
+        self.get_syn_node(i)
+            .expect("canonical e-nodes are only stored if explanations are enabled")
+    }
+
+    // The canonical term of an e-graph that records explanations.
+    #[cfg(feature = "explanations")]
+    pub(crate) fn syn_expr(&self, i: &AppliedId) -> RecExpr<L> {
+        self.get_syn_expr(i)
+            .expect("canonical terms are only stored if explanations are enabled")
     }
 }
 
@@ -434,7 +640,7 @@ impl PendingType {
 // TODO re-enable use<...> when it's stabilized.
 // fn cartesian<'a, T>(input: &'a [Vec<T>]) -> impl Iterator<Item=Vec<&'a T>> /*+ use<'a, T>*/ + '_ {
 #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
-fn cartesian<T>(input: &[Vec<T>]) -> impl Iterator<Item = Vec<&T>> + '_ {
+pub(crate) fn cartesian<T>(input: &[Vec<T>]) -> impl Iterator<Item = Vec<&T>> + '_ {
     let n = input.len();
     let mut indices = vec![0; n];
     let mut done = false;
@@ -463,3 +669,14 @@ fn cartesian1() {
     let vals = cartesian(&v);
     assert_eq!(vals.count(), 4);
 }
+
+// Names the slots `$0`, `$1`, ... in sorted order.
+// This keeps the relative order of fresh slots, but not their absolute numbers.
+pub(crate) fn canonical_names(slots: &SmallHashSet<Slot>) -> SlotMap {
+    let mut v: Vec<Slot> = slots.iter().copied().collect();
+    v.sort();
+    v.into_iter()
+        .enumerate()
+        .map(|(k, s)| (s, Slot::numeric(k as u32)))
+        .collect()
+}
@@ -7,25 +7,27 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         &mut self,
         i: Id,
         cap: &SmallHashSet<Slot>,
-        #[allow(unused)] proof: ProvenEq,
+        #[allow(unused)] proof: Option<ProvenEq>,
     ) {
         if CHECKS {
             assert!(self.is_alive(i));
 
             #[cfg(feature = "explanations")]
-            assert_eq!(proof.l.id, i);
+            if let Some(proof) = &proof {
+                assert_eq!(proof.l.id, i);
+            }
         }
 
         #[cfg(feature = "explanations")]
-        let prf = {
+        let old_prf = self
+            .proven_find_applied_id(&self.mk_syn_identity_applied_id(i))
+            .proof;
+        #[cfg(feature = "explanations")]
+        let prf = proof.zip(old_prf).map(|(proof, old_prf)| {
             let flipped = prove_symmetry(proof.clone(), &self.proof_registry);
             let new_prf = prove_transitivity(proof, flipped, &self.proof_registry);
-
-            let old_prf = self
-                .proven_find_applied_id(&self.mk_syn_identity_applied_id(i))
-                .proof;
             prove_transitivity(new_prf, old_prf, &self.proof_registry)
-        };
+        });
 
         let elem = self
             .mk_syn_identity_applied_id(i)
@@ -33,9 +35,11 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
         #[cfg(feature = "explanations")]
         if CHECKS {
-            let eq = prf.equ();
-            let elem2 = eq.r.apply_slotmap_partial(&eq.l.m.inverse());
-            assert_eq!(elem, elem2);
+            if let Some(prf) = &prf {
+                let eq = prf.equ();
+                let elem2 = eq.r.apply_slotmap_partial(&eq.l.m.inverse());
+                assert_eq!(elem, elem2);
+            }
         }
 
         self.unionfind_set(
@@ -55,11 +59,13 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         &mut self,
         from: &AppliedId,
         cap: &SmallHashSet<Slot>,
-        proof: ProvenEq,
+        proof: Option<ProvenEq>,
     ) {
         #[cfg(feature = "explanations")]
         if CHECKS {
-            assert_eq!(from.id, proof.l.id);
+            if let Some(proof) = &proof {
+                assert_eq!(from.id, proof.l.id);
+            }
         }
 
         let origcap = cap.iter().map(|x| from.m.inverse()[*x]).collect();
@@ -107,14 +113,12 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
                 .filter(|(x, _)| cap.contains(x))
                 .collect();
 
-            #[cfg(feature = "explanations")]
-            let prf = self.disassociate_proven_eq(proven_perm.proof);
             let out = ProvenPerm {
                 elem: perm,
                 #[cfg(feature = "explanations")]
-                proof: prf,
-                #[cfg(feature = "explanations")]
-                reg: self.proof_registry.clone(),
+                proof: proven_perm
+                    .proof
+                    .map(|(p, reg)| (self.disassociate_proven_eq(p), reg)),
             };
             if CHECKS {
                 out.check();
@@ -123,12 +127,13 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         };
 
         let generators = generators.into_iter().map(restrict_proven).collect();
-        let identity = ProvenPerm::identity(id, &cap, syn_slots, self.proof_registry.clone());
+        let identity = ProvenPerm::identity(id, &cap, syn_slots, &self.proof_registry);
         if CHECKS {
             identity.check();
         }
         let c = self.classes.get_mut(&id).unwrap();
         c.group = Group::new(&identity, generators);
+        self.mark_changed(id);
 
         self.touched_class(from.id, PendingType::Full);
     }
@@ -179,7 +184,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         // i.m :: slots(i) -> X
         // i_orig.m :: slots(i_orig) -> X
         if !i.slots().is_subset(&enode.slots()) {
-            self.handle_shrink_in_upwards_merge(src_id);
+            self.handle_shrink_in_upwards_merge(self.pc_from_node(src_id, i_orig.id, &node));
 
             enode = self.find_enode(&enode);
             i = self.find_applied_id(&i);
@@ -189,7 +194,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
         // upwards merging found a match!
         if self.lookup_internal(&t).is_some() {
-            self.handle_congruence(self.pc_from_src_id(src_id));
+            self.handle_congruence(self.pc_from_node(src_id, i_orig.id, &node));
             return;
         }
 
@@ -202,27 +207,39 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             }
         }
         let bij = bij.compose(&m);
-        let t = (sh, bij);
-        self.raw_add_to_class(i.id, t.clone(), src_id);
+        let node = sh.apply_slotmap(&bij);
+        self.raw_add_to_class(i.id, (sh, bij), src_id);
 
-        self.determine_self_symmetries(src_id);
+        self.determine_self_symmetries(self.pc_from_node(src_id, i.id, &node));
     }
 
     fn update_analysis(&mut self, sh: &L, i: Id) {
         let v = N::make(self, sh);
 
         let c = self.classes.get_mut(&i).unwrap();
-        let old = c.analysis_data.clone();
-        let new = N::merge(old.clone(), v);
-        c.analysis_data = new.clone();
-
-        if new != old {
+        let (DidMerge(changed, _), conflict) =
+            c.analysis_data.as_mut().unwrap().try_merge_partial(v);
+        if let Some(reason) = conflict {
+            // the e-node came into this e-class through the unionfind entry of its original e-class.
+            let src_id = c.nodes[sh].src_id;
+            #[cfg(feature = "explanations")]
+            let proof = self
+                .proven_find_applied_id(&self.mk_syn_identity_applied_id(src_id))
+                .proof;
+            self.record_analysis_conflict(AnalysisConflict {
+                l: src_id,
+                r: i,
+                reason,
+                #[cfg(feature = "explanations")]
+                proof,
+            });
+        }
+        if changed {
             self.touched_class(i, PendingType::OnlyAnalysis);
         }
     }
 
-    fn handle_shrink_in_upwards_merge(&mut self, src_id: Id) {
-        let pc1 = self.pc_from_src_id(src_id);
+    fn handle_shrink_in_upwards_merge(&mut self, pc1: ProvenContains<L>) {
         let pc2 = self.chain_pc_map(&pc1, |_, pai| self.proven_proven_find_applied_id(&pai));
 
         let (a, b, prf) = self.pc_congruence(&pc1, &pc2);
@@ -234,9 +251,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
     // finds self-symmetries caused by the e-node `src_id`.
     #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
-    fn determine_self_symmetries(&mut self, src_id: Id) {
-        let pc1 = self.pc_from_src_id(src_id);
-
+    fn determine_self_symmetries(&mut self, pc1: ProvenContains<L>) {
         let i = pc1.target_id();
         let weak = pc1.node.elem.weak_shape().0;
         for pn2 in self.proven_proven_get_group_compatible_variants(&pc1.node) {
@@ -262,15 +277,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
                 // or is it the opposite direction? (flip a with b)
                 let perm = a.m.compose(&b.m.inverse());
 
-                let proven_perm = ProvenPerm {
-                    elem: perm,
-
-                    #[cfg(feature = "explanations")]
-                    proof,
-
-                    #[cfg(feature = "explanations")]
-                    reg: self.proof_registry.clone(),
-                };
+                let proven_perm = ProvenPerm::new(perm, proof, &self.proof_registry);
 
                 if CHECKS {
                     proven_perm.check();
@@ -295,7 +302,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     // upon touching an e-class, you need to update all usages of it.
     #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
     pub(crate) fn touched_class(&mut self, i: Id, pending_ty: PendingType) {
-        for sh in &self.classes[&i].usages {
+        for sh in self.classes[&i].usages.keys() {
             let v = self.pending.entry(sh.clone()).or_insert(pending_ty);
             *v = v.merge(pending_ty);
         }
@@ -307,9 +314,9 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             .hashcons
             .get(&sh)
             .expect("pc_from_shape should only be called if the shape exists in the e-graph!");
-        let c = self.classes[&i].nodes[&sh].src_id;
+        let psn = &self.classes[&i].nodes[&sh];
 
         // this shall change! Later on we want to deprecate the src-id.
-        self.pc_from_src_id(c)
+        self.pc_from_node(psn.src_id, *i, &sh.apply_slotmap(&psn.elem))
     }
 }
@@ -0,0 +1,304 @@
+use super::canonical_names;
+use crate::*;
+use serde_json::{json, Map, Value};
+
+/// A cost that can be written into the egraph-serialize format, which stores costs as `f64`.
+pub trait SerializeCost {
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_serialize_cost {
+    ($($t:ty),*) => {
+        $(
+            impl SerializeCost for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_serialize_cost!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+#[derive(Debug)]
+pub enum SerializeError {
+    Json(serde_json::Error),
+
+    /// The JSON value doesn't follow the egraph-serialize format.
+    Format(String),
+
+    /// The node with this id couldn't be converted into an e-node of the [Language].
+    Syntax(String),
+
+    /// The node with this id uses slots, which the importer doesn't support.
+    HasSlots(String),
+}
+
+impl From<serde_json::Error> for SerializeError {
+    fn from(e: serde_json::Error) -> Self {
+        SerializeError::Json(e)
+    }
+}
+
+impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// Exports the e-graph in the JSON format of [egraph-serialize](https://github.com/egraphs-good/egraph-serialize),
+    /// as used by the extraction-gym.
+    ///
+    /// Node `j` of e-class `i` gets the id `"i.j"`, and each child points to the first node of its e-class.
+    /// Nodes with a child e-class that has no exported node are left out, so that every child points to an existing node.
+    ///
+    /// The format expects the cost of a term to be the sum of the costs of its nodes.
+    /// Hence the cost of a node is the cost that `cost_fn` assigns to it with the best costs of its children, minus these child costs.
+    /// For additive cost functions like [AstSize], this is the cost of the node itself.
+    /// Children without a finite term count with the default cost.
+    ///
+    /// The slots are stored in additional fields of each node, which other tools ignore.
+    /// The slots of each e-class are named `$0`, `$1`, ... as in [EGraph::dump_to], and the other slots of a node are numbered behind them.
+    /// - `"eclass_slots"`: the slots of the e-class of this node.
+    /// - `"slot_args"`: the slots written directly in the node, like the binder of a `lam`.
+    /// - `"child_slotmaps"`: for each child, the [SlotMap] from the slots of the child e-class to the slots of this node.
+    pub fn to_serialized_json<CF: CostFunction<L>>(&self, cost_fn: &CF, roots: &[Id]) -> String
+    where
+        CF::Cost: SerializeCost + Default,
+    {
+        let best = best_costs(self, cost_fn);
+        let class_names = |j: Id| canonical_names(&self.classes[&j].slots);
+
+        // the e-nodes of each e-class that get exported, i.e. whose children all have an exported node.
+        let mut exported: HashMap<Id, Vec<L>> = self
+            .ids()
+            .into_iter()
+            .map(|i| (i, self.sorted_enodes(i)))
+            .collect();
+        loop {
+            let empty: HashSet<Id> = exported
+                .iter()
+                .filter(|(_, v)| v.is_empty())
+                .map(|(i, _)| *i)
+                .collect();
+            let mut changed = false;
+            for v in exported.values_mut() {
+                let len = v.len();
+                v.retain(|n| n.ids().iter().all(|c| !empty.contains(c)));
+                changed |= v.len() != len;
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut nodes = Map::new();
+        for i in self.ids() {
+            let names = class_names(i);
+            let eclass_slots: Vec<String> = (0..names.len())
+                .map(|k| Slot::numeric(k as u32).to_string())
+                .collect();
+
+            for (j, n) in exported[&i].iter().enumerate() {
+                let cost = cost_fn.cost(n, |c| best.get(&c).cloned().unwrap_or_default());
+                let child_costs: f64 = n
+                    .ids()
+                    .iter()
+                    .map(|c| best.get(c).cloned().unwrap_or_default().to_f64())
+                    .sum();
+                let cost = cost.to_f64() - child_costs;
+
+                let n = self.canonical_enode(n, &names, &class_names);
+                let mut op = Vec::new();
+                let mut slot_args = Vec::new();
+                for x in n.to_syntax() {
+                    match x {
+                        SyntaxElem::String(s) => op.push(s),
+                        SyntaxElem::Slot(s) => slot_args.push(s.to_string()),
+                        SyntaxElem::AppliedId(_) => {}
+                    }
+                }
+
+                let mut children = Vec::new();
+                let mut child_slotmaps = Vec::new();
+                for child in n.applied_id_occurrences() {
+                    children.push(Value::String(format!("{}.0", child.id.0)));
+                    let m: Map<String, Value> = child
+                        .m
+                        .iter()
+                        .map(|(x, y)| (x.to_string(), Value::String(y.to_string())))
+                        .collect();
+                    child_slotmaps.push(Value::Object(m));
+                }
+
+                let node = json!({
+                    "op": op.join(" "),
+                    "children": children,
+                    "eclass": i.0.to_string(),
+                    "cost": cost,
+                    "eclass_slots": eclass_slots,
+                    "slot_args": slot_args,
+                    "child_slotmaps": child_slotmaps,
+                });
+                nodes.insert(format!("{}.{j}", i.0), node);
+            }
+        }
+
+        let roots: Vec<String> = roots
+            .iter()
+            .map(|i| self.find_id(*i).0.to_string())
+            .collect();
+        let out = json!({
+            "nodes": nodes,
+            "root_eclasses": roots,
+        });
+        serde_json::to_string_pretty(&out).unwrap()
+    }
+
+    /// Imports a slot-free e-graph in the JSON format of [egraph-serialize](https://github.com/egraphs-good/egraph-serialize).
+    ///
+    /// The `op` of each node is split at whitespace and followed by its children to obtain the [SyntaxElem]s that are passed to [Language::from_syntax].
+    /// Nodes that are only reachable through cycles, and hence represent no finite term, are dropped.
+    ///
+    /// Returns the e-graph together with the e-classes listed in `root_eclasses`.
+    pub fn from_serialized_json(s: &str) -> Result<(Self, Vec<AppliedId>), SerializeError> {
+        let v: Value = serde_json::from_str(s)?;
+        let nodes = v
+            .get("nodes")
+            .and_then(Value::as_object)
+            .ok_or_else(|| SerializeError::Format(String::from("missing `nodes`")))?;
+
+        let eclass_of = |node_id: &str| -> Result<&str, SerializeError> {
+            nodes
+                .get(node_id)
+                .and_then(|n| n.get("eclass"))
+                .and_then(Value::as_str)
+                .ok_or_else(|| SerializeError::Format(format!("node `{node_id}` has no eclass")))
+        };
+
+        // (node id, eclass, op, child eclasses)
+        let mut pending = Vec::new();
+        for (node_id, n) in nodes {
+            if has_slots(n) {
+                return Err(SerializeError::HasSlots(node_id.clone()));
+            }
+            let op = n
+                .get("op")
+                .and_then(Value::as_str)
+                .ok_or_else(|| SerializeError::Format(format!("node `{node_id}` has no op")))?;
+            let children = n
+                .get("children")
+                .and_then(Value::as_array)
+                .map(|c| c.iter().map(Value::as_str).collect::<Option<Vec<_>>>())
+                .unwrap_or(Some(Vec::new()))
+                .ok_or_else(|| {
+                    SerializeError::Format(format!("node `{node_id}` has invalid children"))
+                })?;
+            let children = children
+                .into_iter()
+                .map(eclass_of)
+                .collect::<Result<Vec<_>, _>>()?;
+            pending.push((node_id, eclass_of(node_id)?, op, children));
+        }
+
+        // A node can be added, once all of its children e-classes contain an added node.
+        let mut eg = EGraph::new();
+        let mut added: HashMap<&str, AppliedId> = HashMap::default();
+        loop {
+            let mut progress = false;
+            let mut rest = Vec::new();
+            for (node_id, eclass, op, children) in pending {
+                if !children.iter().all(|c| added.contains_key(c)) {
+                    rest.push((node_id, eclass, op, children));
+                    continue;
+                }
+
+                let mut syntax: Vec<SyntaxElem> = op
+                    .split_whitespace()
+                    .map(|x| SyntaxElem::String(x.to_string()))
+                    .collect();
+                syntax.extend(
+                    children
+                        .iter()
+                        .map(|c| SyntaxElem::AppliedId(added[c].clone())),
+                );
+                let enode = L::from_syntax(&syntax)
+                    .ok_or_else(|| SerializeError::Syntax(node_id.clone()))?;
+
+                let i = eg.add(enode);
+                match added.get(eclass) {
+                    Some(j) => {
+                        eg.union(&i, j);
+                    }
+                    None => {
+                        added.insert(eclass, i);
+                    }
+                }
+                progress = true;
+            }
+            pending = rest;
+            if !progress {
+                break;
+            }
+        }
+
+        let roots = v
+            .get("root_eclasses")
+            .and_then(Value::as_array)
+            .map(|r| r.as_slice())
+            .unwrap_or_default();
+        let mut out_roots = Vec::new();
+        for r in roots {
+            let r = r
+                .as_str()
+                .ok_or_else(|| SerializeError::Format(format!("invalid root eclass {r}")))?;
+            let i = added.get(r).ok_or_else(|| {
+                SerializeError::Format(format!("root eclass `{r}` contains no finite term"))
+            })?;
+            out_roots.push(eg.find_applied_id(i));
+        }
+
+        Ok((eg, out_roots))
+    }
+}
+
+// The cost of the best term of each e-class that has a finite term.
+// Like Bellman-Ford, the best terms are found after one round per e-class, if costs grow with the children.
+fn best_costs<L: Language, N: Analysis<L>, CF: CostFunction<L>>(
+    eg: &EGraph<L, N>,
+    cost_fn: &CF,
+) -> HashMap<Id, CF::Cost> {
+    let mut best: HashMap<Id, CF::Cost> = HashMap::default();
+    for _ in 0..=eg.ids().len() {
+        let mut changed = false;
+        for i in eg.ids() {
+            for n in eg.enodes(i) {
+                if !n.ids().iter().all(|c| best.contains_key(c)) {
+                    continue;
+                }
+                let c = cost_fn.cost(&n, |c| best[&c].clone());
+                if best.get(&i).is_none_or(|old| c < *old) {
+                    best.insert(i, c);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    best
+}
+
+// whether the slot metadata written by `to_serialized_json` is non-empty.
+fn has_slots(n: &Value) -> bool {
+    let non_empty_array = |k: &str| {
+        n.get(k)
+            .and_then(Value::as_array)
+            .is_some_and(|a| !a.is_empty())
+    };
+    let non_empty_map = n
+        .get("child_slotmaps")
+        .and_then(Value::as_array)
+        .is_some_and(|a| {
+            a.iter()
+                .any(|m| m.as_object().is_some_and(|m| !m.is_empty()))
+        });
+    non_empty_array("eclass_slots") || non_empty_array("slot_args") || non_empty_map
+}
@@ -26,17 +26,32 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         subst: &Subst,
         #[allow(unused)] justification: Option<String>,
     ) -> bool {
-        let a = pattern_subst(self, from_pat, subst);
-        let b = pattern_subst(self, to_pat, subst);
+        #[cfg(feature = "explanations")]
+        if self.explanations_enabled() {
+            // Name the redundant slots of the matched e-classes once,
+            // so that both sides of the explicit proof agree with the recorded `subst`.
+            let subst = subst
+                .iter()
+                .map(|(x, i)| (x.clone(), self.synify_app_id(i.clone())))
+                .collect::<Subst>();
+
+            let a = pattern_subst(self, from_pat, &subst);
+            let b = pattern_subst(self, to_pat, &subst);
+
+            let syn_a = self.synify_app_id(a.clone());
+            let syn_b = self.synify_app_id(b.clone());
+
+            let proof = self.prove_explicit(&syn_a, &syn_b, justification, Some(subst));
 
-        #[allow(unused)]
-        let syn_a = self.synify_app_id(a.clone());
-        #[allow(unused)]
-        let syn_b = self.synify_app_id(b.clone());
+            let out = self.union_internal(&a, &b, Some(proof));
+            self.rebuild_called_from_union_instantiations();
+            return out;
+        }
 
-        let proof = ghost!(self.prove_explicit(&syn_a, &syn_b, justification));
+        let a = pattern_subst(self, from_pat, subst);
+        let b = pattern_subst(self, to_pat, subst);
 
-        let out = self.union_internal(&a, &b, proof);
+        let out = self.union_internal(&a, &b, None);
         self.rebuild_called_from_union_instantiations();
         out
     }
@@ -51,31 +66,34 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         &mut self,
         l: &AppliedId,
         r: &AppliedId,
-        #[allow(unused)] proof: ProvenEq,
+        #[allow(unused)] proof: Option<ProvenEq>,
     ) -> bool {
         // normalize inputs
         let pai_l = self.proven_find_applied_id(&l);
         let pai_r = self.proven_find_applied_id(&r);
 
-        let proof = ghost!({
-            if CHECKS {
-                pai_l.proof.check(self);
-                pai_r.proof.check(self);
-            }
+        let proof = ghost!(match (pai_l.proof, proof, pai_r.proof) {
+            (Some(prf_l), Some(proof), Some(prf_r)) => {
+                if CHECKS {
+                    prf_l.check(self);
+                    prf_r.check(self);
+                }
 
-            let a = self.prove_symmetry(pai_l.proof);
-            let a = self.prove_transitivity(a, proof);
-            let a = self.prove_transitivity(a, pai_r.proof);
-            if CHECKS {
-                assert_eq!(a.l.id, pai_l.elem.id);
-                assert_eq!(a.r.id, pai_r.elem.id);
+                let a = self.prove_symmetry(prf_l);
+                let a = self.prove_transitivity(a, proof);
+                let a = self.prove_transitivity(a, prf_r);
+                if CHECKS {
+                    assert_eq!(a.l.id, pai_l.elem.id);
+                    assert_eq!(a.r.id, pai_r.elem.id);
+                }
+                Some(a)
             }
-            a
+            _ => None,
         });
         self.union_leaders(pai_l.elem, pai_r.elem, proof)
     }
 
-    fn union_leaders(&mut self, l: AppliedId, r: AppliedId, proof: ProvenEq) -> bool {
+    fn union_leaders(&mut self, l: AppliedId, r: AppliedId, proof: Option<ProvenEq>) -> bool {
         // early return, if union should not be made.
         if self.eq(&l, &r) {
             return false;
@@ -90,7 +108,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         }
 
         if r.slots() != cap {
-            let flipped_proof = ghost!(self.prove_symmetry(proof.clone()));
+            let flipped_proof = ghost!(proof.clone().map(|p| self.prove_symmetry(p)));
             self.shrink_slots(&r, &cap, flipped_proof);
             self.union_internal(&l, &r, proof);
             return true;
@@ -108,17 +126,13 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
                 assert_eq!(&perm.keys(), &self.classes[&id].slots);
             }
 
-            let proven_perm = ProvenPerm {
-                elem: perm,
-                #[cfg(feature = "explanations")]
-                proof,
-                #[cfg(feature = "explanations")]
-                reg: self.proof_registry.clone(),
-            };
+            let proven_perm = ProvenPerm::new(perm, proof, &self.proof_registry);
 
             if CHECKS {
                 #[cfg(feature = "explanations")]
-                assert_eq!(proven_perm.proof.l.id, id);
+                if let Some((proof, _)) = &proven_perm.proof {
+                    assert_eq!(proof.l.id, id);
+                }
 
                 proven_perm.check();
             }
@@ -133,7 +147,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
             true
         } else {
-            let slot_size = |i| self.classes[&i].syn_enode.slots().len();
+            let slot_size = |i| self.classes[&i].syn_slots.len();
 
             let size = |i| {
                 let c = &self.classes[&i];
@@ -160,7 +174,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             if right_order(l.id, r.id) {
                 self.move_to(&l, &r, proof)
             } else {
-                let proof = ghost!(self.prove_symmetry(proof));
+                let proof = ghost!(proof.map(|p| self.prove_symmetry(p)));
                 self.move_to(&r, &l, proof)
             }
 
@@ -174,26 +188,45 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     }
 
     // moves everything from `from` to `to`.
-    fn move_to(&mut self, from: &AppliedId, to: &AppliedId, #[allow(unused)] proof: ProvenEq) {
+    fn move_to(
+        &mut self,
+        from: &AppliedId,
+        to: &AppliedId,
+        #[allow(unused)] proof: Option<ProvenEq>,
+    ) {
         if CHECKS {
             assert_eq!(from.slots(), to.slots());
             #[cfg(feature = "explanations")]
-            assert_eq!(from.id, proof.l.id);
-            #[cfg(feature = "explanations")]
-            assert_eq!(to.id, proof.r.id);
+            if let Some(proof) = &proof {
+                assert_eq!(from.id, proof.l.id);
+                assert_eq!(to.id, proof.r.id);
+            }
         }
 
         {
-            let analysis_from = self.analysis_data(from.id).clone();
-            let analysis_to = self.analysis_data_mut(to.id);
-            let old_analysis_to = analysis_to.clone();
-            let new_analysis_to = N::merge(analysis_from, analysis_to.clone());
-            let changed = old_analysis_to != new_analysis_to;
-            *analysis_to = new_analysis_to;
-
+            // `from` dies, so its data is moved instead of cloned.
+            let analysis_from = self
+                .classes
+                .get_mut(&from.id)
+                .unwrap()
+                .analysis_data
+                .take()
+                .unwrap();
+            let (DidMerge(changed, _), conflict) = self
+                .analysis_data_mut(to.id)
+                .try_merge_partial(analysis_from);
             if changed {
                 self.touched_class(to.id, PendingType::OnlyAnalysis);
             }
+            if let Some(reason) = conflict {
+                self.record_analysis_conflict(AnalysisConflict {
+                    l: from.id,
+                    r: to.id,
+                    reason,
+                    #[cfg(feature = "explanations")]
+                    proof: proof.clone(),
+                });
+            }
         }
 
         // from.m :: slots(from.id) -> X
@@ -257,23 +290,23 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             perm
         };
         #[cfg(feature = "explanations")]
-        let prf = self.proven_find_applied_id(&from).proof;
-        #[cfg(feature = "explanations")]
-        let prf_rev = self.prove_symmetry(prf.clone());
+        let prfs = self.proven_find_applied_id(&from).proof.map(|prf| {
+            let prf_rev = self.prove_symmetry(prf.clone());
+            (prf, prf_rev)
+        });
 
         let change_proven_permutation_from_from_to_to = |proven_perm: ProvenPerm| {
             let new_perm = change_permutation_from_from_to_to(proven_perm.elem);
-            #[cfg(feature = "explanations")]
-            let new_proof = self.prove_transitivity(
-                prf_rev.clone(),
-                self.prove_transitivity(proven_perm.proof, prf.clone()),
-            );
             ProvenPerm {
                 elem: new_perm,
                 #[cfg(feature = "explanations")]
-                proof: new_proof,
-                #[cfg(feature = "explanations")]
-                reg: self.proof_registry.clone(),
+                proof: proven_perm
+                    .proof
+                    .zip(prfs.clone())
+                    .map(|((p, reg), (prf, prf_rev))| {
+                        let p = self.prove_transitivity(p, prf);
+                        (self.prove_transitivity(prf_rev, p), reg)
+                    }),
             }
         };
 
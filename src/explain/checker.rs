@@ -0,0 +1,167 @@
+use crate::*;
+
+/// Re-validates an [ExportedProof] against a set of rewrite rules, independently of the [EGraph] that produced it.
+///
+/// [ExportedProof::check] accepts explicit steps as axioms.
+/// The checker additionally requires every explicit step to be an instance of the rule named by its justification,
+/// under the substitution recorded in [ProofStep::subst].
+/// Hence a bug in the e-graph can't make it accept an equation that doesn't follow from the rules.
+///
+/// Native substitutions `b[x := t]` on the right-hand side of a rule are re-computed on the instantiated terms,
+/// by replacing every occurrence of `x` in `b` with `t`.
+pub struct ProofChecker<L: Language> {
+    rules: HashMap<String, (Pattern<L>, Pattern<L>)>,
+}
+
+impl<L: Language> Default for ProofChecker<L> {
+    fn default() -> Self {
+        Self {
+            rules: HashMap::default(),
+        }
+    }
+}
+
+impl<L: Language> ProofChecker<L> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the rules built from patterns, e.g. using [Rewrite::new].
+    /// Custom rules built from a [RewriteT] are skipped, as the checker can't look into them.
+    pub fn from_rewrites<N: Analysis<L>>(rewrites: &[Rewrite<L, N>]) -> Self {
+        let mut out = Self::new();
+        for rw in rewrites {
+            if let Some((name, lhs, rhs)) = &rw.patterns {
+                out.add_rule(name, lhs.clone(), rhs.clone());
+            }
+        }
+        out
+    }
+
+    /// Adds the rule `lhs => rhs` with the given name.
+    pub fn add_rule(&mut self, name: &str, lhs: Pattern<L>, rhs: Pattern<L>) {
+        self.rules.insert(name.to_string(), (lhs, rhs));
+    }
+
+    /// Checks that `proof` is a valid proof of `lhs = rhs`.
+    ///
+    /// The free slots of the proven equation may be named differently from those of `lhs` and `rhs`.
+    pub fn check(
+        &self,
+        proof: &ExportedProof<L>,
+        lhs: &RecExpr<L>,
+        rhs: &RecExpr<L>,
+    ) -> Result<(), ProofCheckError> {
+        proof.check()?;
+
+        let (l, r) = proof.equation();
+        let mut free = SlotMap::new();
+        let env = Binders::default();
+        if !alpha_match(l, lhs, &env, &mut free) || !alpha_match(r, rhs, &env, &mut free) {
+            return Err(ProofCheckError {
+                step: proof.root,
+                reason: format!("the proof shows {l} = {r} instead"),
+            });
+        }
+
+        for (i, step) in proof.steps.iter().enumerate() {
+            if let ProofRule::Explicit(j) = &step.rule {
+                self.check_explicit(step, j)
+                    .map_err(|reason| ProofCheckError { step: i, reason })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_explicit(&self, step: &ProofStep<L>, j: &Option<String>) -> Result<(), String> {
+        let Some(name) = j else {
+            return Err(String::from("the step has no justification"));
+        };
+        let Some((lhs, rhs)) = self.rules.get(name) else {
+            return Err(format!("unknown rule `{name}`"));
+        };
+        let Some(subst) = &step.subst else {
+            return Err(String::from("the step has no substitution"));
+        };
+
+        let mut free = SlotMap::new();
+        let env = Binders::default();
+        if !is_instance(lhs, &step.lhs, subst, &env, &mut free)
+            || !is_instance(rhs, &step.rhs, subst, &env, &mut free)
+        {
+            return Err(format!(
+                "{} = {} is not an instance of `{name}`",
+                step.lhs, step.rhs
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Checks whether `re` is `pat` instantiated with `subst`, up to renaming of bound slots. See `alpha_match`.
+fn is_instance<L: Language>(
+    pat: &Pattern<L>,
+    re: &RecExpr<L>,
+    subst: &HashMap<String, RecExpr<L>>,
+    env: &Binders,
+    free: &mut SlotMap,
+) -> bool {
+    match pat {
+        Pattern::ENode(n, children) => {
+            let mut env = env.clone();
+            children.len() == re.children.len()
+                && match_node(n, &re.node, &mut env, free)
+                && children
+                    .iter()
+                    .zip(&re.children)
+                    .all(|(p, c)| is_instance(p, c, subst, &env, free))
+        }
+        Pattern::PVar(v) => subst.get(v).is_some_and(|t| alpha_match(t, re, env, free)),
+        Pattern::Subst(..) => {
+            instantiate(pat, subst).is_some_and(|t| alpha_match(&t, re, env, free))
+        }
+    }
+}
+
+// The term `pat[subst]`, or `None` if a pattern variable is missing in `subst`.
+fn instantiate<L: Language>(
+    pat: &Pattern<L>,
+    subst: &HashMap<String, RecExpr<L>>,
+) -> Option<RecExpr<L>> {
+    match pat {
+        Pattern::ENode(n, children) => Some(RecExpr {
+            node: n.clone(),
+            children: children
+                .iter()
+                .map(|c| instantiate(c, subst))
+                .collect::<Option<_>>()?,
+        }),
+        Pattern::PVar(v) => subst.get(v).cloned(),
+        Pattern::Subst(b, x, t) => {
+            let b = instantiate(b, subst)?;
+            let x = instantiate(x, subst)?;
+            let t = instantiate(t, subst)?;
+            Some(term_subst(&b, &x, &t))
+        }
+    }
+}
+
+// returns re[x := t], replacing the subterms of `re` that are syntactically equal to `x`.
+fn term_subst<L: Language>(re: &RecExpr<L>, x: &RecExpr<L>, t: &RecExpr<L>) -> RecExpr<L> {
+    if same_term(re, x) {
+        return t.clone();
+    }
+    RecExpr {
+        node: re.node.clone(),
+        children: re.children.iter().map(|c| term_subst(c, x, t)).collect(),
+    }
+}
+
+fn same_term<L: Language>(a: &RecExpr<L>, b: &RecExpr<L>) -> bool {
+    a.children.len() == b.children.len()
+        && nullify_app_ids(&a.node) == nullify_app_ids(&b.node)
+        && a.children
+            .iter()
+            .zip(&b.children)
+            .all(|(x, y)| same_term(x, y))
+}
@@ -0,0 +1,360 @@
+use crate::*;
+
+/// A proof written in terms of syntactic terms instead of e-classes, so that it can be checked and stored independently of the [EGraph].
+///
+/// The proof is a DAG: each step proves the equation `lhs = rhs` by a [ProofRule] from the steps listed in `children`,
+/// which always come before it. Sub-proofs that are used multiple times are only stored once.
+///
+/// An equation holds for all names of its free slots, so each child is renamed before it's used.
+/// The `k`-th renaming maps the free slots of the `k`-th child to the slots of this step:
+/// - [ProofRule::Symmetry]: the child, renamed, proves `rhs = lhs`.
+/// - [ProofRule::Transitivity]: the children, renamed, prove `lhs = mid` and `mid = rhs` for some term `mid`.
+/// - [ProofRule::Congruence]: `lhs` and `rhs` have the same top-level node, and the `k`-th child, renamed, proves that their `k`-th children are equal.
+///   The children of `rhs` are compared within the binders of `lhs`, i.e. the bound slots of both top-level nodes are paired up.
+///
+/// Terms are compared up to renaming of bound slots. A free slot never matches a bound one, so no step can capture a free slot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportedProof<L: Language> {
+    pub steps: Vec<ProofStep<L>>,
+
+    /// The step that proves the whole equation.
+    pub root: usize,
+}
+
+/// A single step of an [ExportedProof].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofStep<L: Language> {
+    pub lhs: RecExpr<L>,
+    pub rhs: RecExpr<L>,
+    pub rule: ProofRule,
+    pub children: Vec<usize>,
+    pub renamings: Vec<SlotMap>,
+
+    /// For explicit steps that apply a rewrite rule, the terms its pattern variables were instantiated with.
+    pub subst: Option<HashMap<String, RecExpr<L>>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofRule {
+    /// An axiom, typically an application of a rewrite rule, with its justification.
+    Explicit(Option<String>),
+    Reflexivity,
+    Symmetry,
+    Transitivity,
+    Congruence,
+}
+
+/// The error returned by [ExportedProof::check].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofCheckError {
+    /// The index of the invalid step.
+    pub step: usize,
+    pub reason: String,
+}
+
+impl ProvenEqRaw {
+    /// Exports the proof, see [ExportedProof].
+    pub fn export<L: Language, N: Analysis<L>>(&self, eg: &EGraph<L, N>) -> ExportedProof<L> {
+        let mut index: HashMap<*const ProvenEqRaw, usize> = HashMap::default();
+        let mut steps = Vec::new();
+        let mut stack: Vec<&ProvenEqRaw> = vec![self];
+
+        'outer: while let Some(x) = stack.last().cloned() {
+            if index.contains_key(&(x as *const ProvenEqRaw)) {
+                stack.pop();
+                continue;
+            }
+
+            let mut children = Vec::new();
+            for sub in x.subproofs() {
+                match index.get(&(&**sub as *const ProvenEqRaw)) {
+                    Some(i) => children.push(*i),
+                    None => {
+                        stack.push(sub);
+                        continue 'outer;
+                    }
+                }
+            }
+
+            let mut subst = None;
+            let (rule, renamings) = match x.proof() {
+                Proof::Explicit(ExplicitProof(j, s)) => {
+                    subst = s
+                        .as_ref()
+                        .map(|s| s.iter().map(|(v, i)| (v.clone(), eg.syn_expr(i))).collect());
+                    (ProofRule::Explicit(j.clone()), vec![])
+                }
+                Proof::Reflexivity(ReflexivityProof) => (ProofRule::Reflexivity, vec![]),
+                Proof::Symmetry(SymmetryProof(c)) => {
+                    let theta = match_app_id(&c.l, &x.r)
+                        .try_union(&match_app_id(&c.r, &x.l))
+                        .unwrap();
+                    (ProofRule::Symmetry, vec![theta])
+                }
+                Proof::Transitivity(TransitivityProof(c1, c2)) => {
+                    let (theta1, theta2) = transitivity_renamings(x, c1, c2);
+                    (ProofRule::Transitivity, vec![theta1, theta2])
+                }
+                Proof::Congruence(CongruenceProof(cs)) => {
+                    let l = eg.syn_node(&x.l);
+                    let r = eg.syn_node(&x.r);
+                    let bound = bound_renaming(&r, &l).unwrap();
+                    let renamings = cs
+                        .iter()
+                        .zip(l.applied_id_occurrences())
+                        .zip(r.applied_id_occurrences())
+                        .map(|((c, ll), rr)| {
+                            let mut rr = rr.clone();
+                            for s in rr.m.values_mut() {
+                                *s = bound.get(*s).unwrap_or(*s);
+                            }
+                            match_app_id(&c.l, ll)
+                                .try_union(&match_app_id(&c.r, &rr))
+                                .unwrap()
+                        })
+                        .collect();
+                    (ProofRule::Congruence, renamings)
+                }
+            };
+
+            index.insert(x as *const ProvenEqRaw, steps.len());
+            steps.push(ProofStep {
+                lhs: eg.syn_expr(&x.l),
+                rhs: eg.syn_expr(&x.r),
+                rule,
+                children,
+                renamings,
+                subst,
+            });
+            stack.pop();
+        }
+
+        ExportedProof {
+            root: steps.len() - 1,
+            steps,
+        }
+    }
+}
+
+impl<L: Language> ExportedProof<L> {
+    /// The equation proven by this proof.
+    pub fn equation(&self) -> (&RecExpr<L>, &RecExpr<L>) {
+        let root = &self.steps[self.root];
+        (&root.lhs, &root.rhs)
+    }
+
+    /// Checks that every step follows from its children by its rule.
+    ///
+    /// Explicit steps are accepted as axioms.
+    pub fn check(&self) -> Result<(), ProofCheckError> {
+        if self.root >= self.steps.len() {
+            return Err(ProofCheckError {
+                step: self.root,
+                reason: String::from("the root step doesn't exist"),
+            });
+        }
+        for i in 0..self.steps.len() {
+            self.check_step(i)
+                .map_err(|reason| ProofCheckError { step: i, reason })?;
+        }
+        Ok(())
+    }
+
+    fn check_step(&self, i: usize) -> Result<(), String> {
+        let step = &self.steps[i];
+        let arity = match step.rule {
+            ProofRule::Explicit(_) | ProofRule::Reflexivity => Some(0),
+            ProofRule::Symmetry => Some(1),
+            ProofRule::Transitivity => Some(2),
+            ProofRule::Congruence => None,
+        };
+        if arity.is_some_and(|n| step.children.len() != n) {
+            return Err(format!("expected {} children", arity.unwrap()));
+        }
+        if step.renamings.len() != step.children.len() {
+            return Err(String::from("expected one renaming per child"));
+        }
+        if let Some(c) = step.children.iter().find(|c| **c >= i) {
+            return Err(format!("child {c} doesn't come before this step"));
+        }
+
+        // the equations of the children, renamed into this step.
+        // Their bound slots get fresh names, so that they can't capture the renamed free slots.
+        let children: Vec<(RecExpr<L>, RecExpr<L>)> = step
+            .children
+            .iter()
+            .zip(&step.renamings)
+            .map(|(c, m)| {
+                let c = &self.steps[*c];
+                (rename_free_slots(&c.lhs, m), rename_free_slots(&c.rhs, m))
+            })
+            .collect();
+
+        let ok = match &step.rule {
+            ProofRule::Explicit(_) => true,
+            ProofRule::Reflexivity => alpha_eq(&step.lhs, &step.rhs),
+            ProofRule::Symmetry => {
+                let (a, b) = &children[0];
+                alpha_eq(a, &step.rhs) && alpha_eq(b, &step.lhs)
+            }
+            ProofRule::Transitivity => {
+                let (a1, b1) = &children[0];
+                let (a2, b2) = &children[1];
+                alpha_eq(a1, &step.lhs) && alpha_eq(b1, a2) && alpha_eq(b2, &step.rhs)
+            }
+            ProofRule::Congruence => {
+                let (l, r) = (&step.lhs, &step.rhs);
+                let Some(bound) = bound_renaming(&r.node, &l.node) else {
+                    return Err(String::from("the top-level nodes differ"));
+                };
+                // the children of `l` and `r` are compared under their own binders, so that none of them captures a free slot.
+                let mut binders = Binders::default();
+                for (x, y) in bound.iter() {
+                    binders.bind(y, x);
+                }
+                let mut r_node = r.node.clone();
+                for s in r_node.all_slot_occurrences_mut() {
+                    *s = bound.get(*s).unwrap_or(*s);
+                }
+                if nullify_app_ids(&l.node) != nullify_app_ids(&r_node) {
+                    return Err(String::from("the top-level nodes differ"));
+                }
+                if children.len() != l.children.len() {
+                    return Err(format!("expected {} children", l.children.len()));
+                }
+                children
+                    .iter()
+                    .zip(l.children.iter().zip(&r.children))
+                    .all(|((a, b), (lc, rc))| alpha_eq(a, lc) && alpha_eq_in(b, rc, &binders))
+            }
+        };
+        if !ok {
+            return Err(format!(
+                "{:?} doesn't prove {} = {}",
+                step.rule, step.lhs, step.rhs
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Maps the bound slots of `a` to the bound slots of `b` by the position of their first occurrence,
+// if `a` and `b` have the same number of them.
+fn bound_renaming<L: Language>(a: &L, b: &L) -> Option<SlotMap> {
+    let distinct = |x: &L| {
+        let mut out: Vec<Slot> = Vec::new();
+        for s in x.private_slot_occurrences() {
+            if !out.contains(&s) {
+                out.push(s);
+            }
+        }
+        out
+    };
+    let a = distinct(a);
+    let b = distinct(b);
+    if a.len() != b.len() {
+        return None;
+    }
+    let pairs: Vec<(Slot, Slot)> = a.into_iter().zip(b).collect();
+    Some(SlotMap::from_pairs(&pairs))
+}
+
+// The bound slots in scope while comparing two terms `a` and `b`, see `alpha_match`.
+#[derive(Clone, Default)]
+pub(crate) struct Binders {
+    // maps the bound slots of `a` to the corresponding bound slots of `b`,
+    env: SlotMap,
+
+    // and back. A free slot must not be matched with a bound one, so `b` needs its own map.
+    inv: SlotMap,
+}
+
+impl Binders {
+    // Binds `x` in `a` and `y` in `b` together, and shadows outer binders of the same names.
+    pub(crate) fn bind(&mut self, x: Slot, y: Slot) {
+        // the outer binders that were paired with `x` or `y` can't be referred to anymore.
+        // They are paired with fresh slots instead, so that nothing matches them.
+        if let Some(y0) = self.env.get(x) {
+            self.inv.insert(y0, Slot::fresh());
+        }
+        if let Some(x0) = self.inv.get(y) {
+            self.env.insert(x0, Slot::fresh());
+        }
+        self.env.insert(x, y);
+        self.inv.insert(y, x);
+    }
+}
+
+// Checks whether `a` is equal to `b` up to renaming of bound slots, after renaming its free slots using `free`.
+// `free` is extended as needed, as long as it stays injective. Free slots are never matched with bound ones.
+pub(crate) fn alpha_match<L: Language>(
+    a: &RecExpr<L>,
+    b: &RecExpr<L>,
+    binders: &Binders,
+    free: &mut SlotMap,
+) -> bool {
+    let mut binders = binders.clone();
+    a.children.len() == b.children.len()
+        && match_node(&a.node, &b.node, &mut binders, free)
+        && a.children
+            .iter()
+            .zip(&b.children)
+            .all(|(x, y)| alpha_match(x, y, &binders, free))
+}
+
+// Like `alpha_match`, but only for the top-level nodes. The bound slots of `a` and `b` are added to `binders`.
+pub(crate) fn match_node<L: Language>(
+    a: &L,
+    b: &L,
+    binders: &mut Binders,
+    free: &mut SlotMap,
+) -> bool {
+    let Some(bound) = bound_renaming(a, b) else {
+        return false;
+    };
+    for (x, y) in bound.iter() {
+        binders.bind(x, y);
+    }
+    let xs = a.all_slot_occurrences();
+    let ys = b.all_slot_occurrences();
+    if xs.len() != ys.len() {
+        return false;
+    }
+    for (x, y) in xs.into_iter().zip(ys) {
+        if let Some(z) = binders.env.get(x) {
+            if z != y {
+                return false;
+            }
+            continue;
+        }
+        if binders.inv.contains_key(y) {
+            return false;
+        }
+        match free.get(x) {
+            Some(z) if z != y => return false,
+            Some(_) => {}
+            None => {
+                if free.values_immut().any(|z| *z == y) {
+                    return false;
+                }
+                free.insert(x, y);
+            }
+        }
+    }
+    let mut node = a.clone();
+    for s in node.all_slot_occurrences_mut() {
+        *s = binders.env.get(*s).or_else(|| free.get(*s)).unwrap();
+    }
+    nullify_app_ids(&node) == nullify_app_ids(b)
+}
+
+// Checks whether `a` and `b` are equal up to renaming of bound slots.
+pub(crate) fn alpha_eq<L: Language>(a: &RecExpr<L>, b: &RecExpr<L>) -> bool {
+    alpha_eq_in(a, b, &Binders::default())
+}
+
+// Like `alpha_eq`, but within the scope of `binders`.
+fn alpha_eq_in<L: Language>(a: &RecExpr<L>, b: &RecExpr<L>, binders: &Binders) -> bool {
+    let mut free = SlotMap::new();
+    alpha_match(a, b, binders, &mut free) && free.iter().all(|(x, y)| x == y)
+}
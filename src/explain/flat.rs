@@ -66,7 +66,7 @@ impl<L: Language> Step<L> {
 impl ProvenEqRaw {
     /// Returns a string representation of a flattened explanation.
     pub fn to_flat_string<L: Language, N: Analysis<L>>(&self, graph: &EGraph<L, N>) -> String {
-        let start = graph.get_syn_expr(&self.l);
+        let start = graph.syn_expr(&self.l);
 
         let mut init_ctx = FlatteningContext {
             head: start.clone(),
@@ -116,7 +116,7 @@ impl ProvenEqRaw {
                 }
                 result
             }
-            Proof::Explicit(ExplicitProof(jus)) => {
+            Proof::Explicit(ExplicitProof(jus, _)) => {
                 let mut subdst = eq.side(/*src:*/ false, symm, graph);
                 subdst.apply_slot_map(&ctx.slot_map);
                 let dst = ctx.head.replace_subexpr(&pos, subdst);
@@ -144,7 +144,7 @@ impl ProvenEqRaw {
     ) -> RecExpr<L> {
         let Equation { l: lhs, r: rhs } = self.equ();
         let subdst_id = if src ^ symm { lhs } else { rhs };
-        graph.get_syn_expr(&subdst_id)
+        graph.syn_expr(&subdst_id)
     }
 }
 
@@ -1,17 +1,21 @@
 use crate::*;
 
+// The prove_X functions are only called if explanations are enabled.
+// Otherwise, no proofs are built and the wrappers store `None` instead, see `ProvenAppliedId`.
+
 #[track_caller]
 pub(crate) fn prove_explicit(
     l: &AppliedId,
     r: &AppliedId,
     j: Option<String>,
+    subst: Option<Subst>,
     reg: &ProofRegistry,
 ) -> ProvenEq {
     let eq = Equation {
         l: l.clone(),
         r: r.clone(),
     };
-    ExplicitProof(j).check(&eq, reg)
+    ExplicitProof(j, subst).check(&eq, reg)
 }
 
 #[track_caller]
@@ -104,7 +108,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     }
 
     pub(crate) fn disassociate_proven_eq(&self, peq: ProvenEq) -> ProvenEq {
-        if self.disassociation_necessary(&peq) {
+        if self.explanations_enabled() && self.disassociation_necessary(&peq) {
             let mut peq = peq;
             let x = self.get_redundancy_proof(peq.l.id);
             let y = self.get_redundancy_proof(peq.r.id);
@@ -120,7 +124,8 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     pub(crate) fn get_redundancy_proof(&self, i: Id) -> ProvenEq {
         let a = self
             .proven_find_applied_id(&self.mk_syn_identity_applied_id(i))
-            .proof;
+            .proof
+            .unwrap();
         let a_rev = prove_symmetry(a.clone(), &self.proof_registry);
 
         prove_transitivity(a, a_rev, &self.proof_registry)
@@ -137,12 +142,13 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         l: &AppliedId,
         r: &AppliedId,
         j: Option<String>,
+        subst: Option<Subst>,
     ) -> ProvenEq {
         if CHECKS {
             self.check_syn_applied_id(l);
             self.check_syn_applied_id(r);
         }
-        self.disassociate_proven_eq(prove_explicit(l, r, j, &self.proof_registry))
+        self.disassociate_proven_eq(prove_explicit(l, r, j, subst, &self.proof_registry))
     }
 
     #[track_caller]
@@ -165,8 +171,8 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
     fn assert_sem_congruence(&self, l: &AppliedId, r: &AppliedId, child_proofs: &[ProvenEq]) {
         // check that the congruence makes sense in "sem".
-        let l_node = alpha_normalize(&self.semify_enode(self.get_syn_node(l)));
-        let r_node = alpha_normalize(&self.semify_enode(self.get_syn_node(r)));
+        let l_node = alpha_normalize(&self.semify_enode(self.syn_node(l)));
+        let r_node = alpha_normalize(&self.semify_enode(self.syn_node(r)));
 
         let null_l = nullify_app_ids(&l_node);
         let null_r = nullify_app_ids(&r_node);
@@ -191,8 +197,8 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     ) -> ProvenEq {
         self.assert_sem_congruence(&l, &r, &child_proofs);
 
-        let l_node = alpha_normalize(&self.get_syn_node(&l));
-        let r_node = alpha_normalize(&self.get_syn_node(&r));
+        let l_node = alpha_normalize(&self.syn_node(&l));
+        let r_node = alpha_normalize(&self.syn_node(&r));
 
         let n = child_proofs.len();
 
@@ -225,8 +231,8 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
         let l_id = self.mk_syn_identity_applied_id(l);
         let r_id = self.mk_syn_identity_applied_id(r);
-        let ll = self.get_syn_node(&l_id);
-        let rr = self.get_syn_node(&r_id);
+        let ll = self.syn_node(&l_id);
+        let rr = self.syn_node(&r_id);
         let l_node = alpha_normalize(&ll);
         let r_node = alpha_normalize(&rr);
 
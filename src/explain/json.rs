@@ -0,0 +1,211 @@
+use crate::*;
+use serde_json::{json, Map, Value};
+
+/// The error returned by [ExportedProof::from_json].
+#[derive(Debug)]
+pub enum ProofJsonError {
+    Json(serde_json::Error),
+
+    /// The JSON value doesn't follow the proof format.
+    Format(String),
+
+    /// The proof was read successfully, but one of its steps is invalid.
+    Check(ProofCheckError),
+}
+
+impl From<serde_json::Error> for ProofJsonError {
+    fn from(e: serde_json::Error) -> Self {
+        ProofJsonError::Json(e)
+    }
+}
+
+impl<L: Language> ExportedProof<L> {
+    /// Writes the proof as JSON.
+    ///
+    /// The format looks as follows:
+    /// ```json
+    /// {
+    ///   "format": "slotted-egraphs-proof",
+    ///   "version": 1,
+    ///   "root": 2,
+    ///   "steps": [
+    ///     { "id": 0, "lhs": "(f $x)", "rhs": "(g $x)", "rule": "explicit", "justification": "f-g", "subst": { "a": "$x" }, "children": [], "renamings": [] },
+    ///     { "id": 1, "lhs": "(g $y)", "rhs": "(f $y)", "rule": "symmetry", "children": [0], "renamings": [{ "$x": "$y" }] },
+    ///     ...
+    ///   ]
+    /// }
+    /// ```
+    /// - `steps` are listed in the order of [ExportedProof::steps], and `id` is the index of each step.
+    /// - `lhs` and `rhs` are terms in the syntax of [RecExpr::parse].
+    /// - `rule` is one of `"explicit"`, `"reflexivity"`, `"symmetry"`, `"transitivity"` and `"congruence"`.
+    /// - `justification` is only present for explicit steps, and may be `null`.
+    /// - `subst` is only present for explicit steps that recorded their substitution, see [ProofStep::subst].
+    /// - `children` lists the ids of the sub-proofs, and `renamings` contains one object for each of them,
+    ///   mapping its free slots to the slots of this step.
+    pub fn to_json(&self) -> String {
+        let steps: Vec<Value> = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                let mut v = json!({
+                    "id": i,
+                    "lhs": step.lhs.to_string(),
+                    "rhs": step.rhs.to_string(),
+                    "rule": rule_name(&step.rule),
+                    "children": step.children,
+                    "renamings": step.renamings.iter().map(|m| {
+                        let m: Map<String, Value> = m
+                            .iter()
+                            .map(|(x, y)| (x.to_string(), Value::String(y.to_string())))
+                            .collect();
+                        Value::Object(m)
+                    }).collect::<Vec<_>>(),
+                });
+                if let ProofRule::Explicit(j) = &step.rule {
+                    v["justification"] = json!(j);
+                }
+                if let Some(subst) = &step.subst {
+                    let m: Map<String, Value> = subst
+                        .iter()
+                        .map(|(x, t)| (x.clone(), Value::String(t.to_string())))
+                        .collect();
+                    v["subst"] = Value::Object(m);
+                }
+                v
+            })
+            .collect();
+
+        let out = json!({
+            "format": "slotted-egraphs-proof",
+            "version": 1,
+            "root": self.root,
+            "steps": steps,
+        });
+        serde_json::to_string_pretty(&out).unwrap()
+    }
+
+    /// Reads a proof written by [ExportedProof::to_json], and re-checks it using [ExportedProof::check].
+    pub fn from_json(s: &str) -> Result<Self, ProofJsonError> {
+        let v: Value = serde_json::from_str(s)?;
+        let format = |msg: String| ProofJsonError::Format(msg);
+
+        if v.get("format").and_then(Value::as_str) != Some("slotted-egraphs-proof") {
+            return Err(format(String::from("missing `format`")));
+        }
+        if v.get("version").and_then(Value::as_u64) != Some(1) {
+            return Err(format(String::from("unsupported `version`")));
+        }
+        let root = v
+            .get("root")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| format(String::from("missing `root`")))? as usize;
+        let steps = v
+            .get("steps")
+            .and_then(Value::as_array)
+            .ok_or_else(|| format(String::from("missing `steps`")))?;
+
+        let mut out = Vec::new();
+        for (i, step) in steps.iter().enumerate() {
+            if step.get("id").and_then(Value::as_u64) != Some(i as u64) {
+                return Err(format(format!("step {i} has the wrong id")));
+            }
+            let term = |k: &str| -> Result<RecExpr<L>, ProofJsonError> {
+                let s = step
+                    .get(k)
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| format(format!("step {i} has no `{k}`")))?;
+                RecExpr::parse(s).map_err(|e| format(format!("step {i}: can't parse `{s}`: {e:?}")))
+            };
+            let lhs = term("lhs")?;
+            let rhs = term("rhs")?;
+
+            let rule = match step.get("rule").and_then(Value::as_str) {
+                Some("explicit") => {
+                    let j = step.get("justification").and_then(Value::as_str);
+                    ProofRule::Explicit(j.map(String::from))
+                }
+                Some("reflexivity") => ProofRule::Reflexivity,
+                Some("symmetry") => ProofRule::Symmetry,
+                Some("transitivity") => ProofRule::Transitivity,
+                Some("congruence") => ProofRule::Congruence,
+                _ => return Err(format(format!("step {i} has an invalid `rule`"))),
+            };
+
+            let children = step
+                .get("children")
+                .and_then(Value::as_array)
+                .and_then(|c| {
+                    c.iter()
+                        .map(|x| x.as_u64().map(|x| x as usize))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| format(format!("step {i} has invalid `children`")))?;
+
+            let subst = match step.get("subst") {
+                None => None,
+                Some(m) => {
+                    let m = m
+                        .as_object()
+                        .ok_or_else(|| format(format!("step {i} has an invalid `subst`")))?;
+                    let mut out = HashMap::default();
+                    for (x, t) in m {
+                        let t = t
+                            .as_str()
+                            .ok_or_else(|| format(format!("step {i} has an invalid `subst`")))?;
+                        let t = RecExpr::parse(t)
+                            .map_err(|e| format(format!("step {i}: can't parse `{t}`: {e:?}")))?;
+                        out.insert(x.clone(), t);
+                    }
+                    Some(out)
+                }
+            };
+
+            let renamings = step
+                .get("renamings")
+                .and_then(Value::as_array)
+                .and_then(|r| r.iter().map(parse_slotmap).collect::<Option<Vec<_>>>())
+                .ok_or_else(|| format(format!("step {i} has invalid `renamings`")))?;
+
+            out.push(ProofStep {
+                lhs,
+                rhs,
+                rule,
+                children,
+                renamings,
+                subst,
+            });
+        }
+
+        let proof = ExportedProof { steps: out, root };
+        proof.check().map_err(ProofJsonError::Check)?;
+        Ok(proof)
+    }
+}
+
+impl ProvenEqRaw {
+    /// Exports the proof as JSON, see [ExportedProof::to_json].
+    pub fn to_json<L: Language, N: Analysis<L>>(&self, eg: &EGraph<L, N>) -> String {
+        self.export(eg).to_json()
+    }
+}
+
+fn rule_name(rule: &ProofRule) -> &'static str {
+    match rule {
+        ProofRule::Explicit(_) => "explicit",
+        ProofRule::Reflexivity => "reflexivity",
+        ProofRule::Symmetry => "symmetry",
+        ProofRule::Transitivity => "transitivity",
+        ProofRule::Congruence => "congruence",
+    }
+}
+
+// e.g. `{"$x": "$y"}`.
+fn parse_slotmap(v: &Value) -> Option<SlotMap> {
+    let slot = |s: &str| s.strip_prefix('$').map(Slot::named);
+    let mut m = SlotMap::new();
+    for (x, y) in v.as_object()? {
+        m.insert(slot(x)?, slot(y.as_str()?)?);
+    }
+    Some(m)
+}
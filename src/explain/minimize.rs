@@ -0,0 +1,275 @@
+use crate::*;
+
+/// How [EGraph::minimize_proof] shortens a proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ProofMinimization {
+    /// Removes detours: double symmetries, reflexivity steps, and parts of transitivity chains that return to a term they already visited.
+    #[default]
+    Simplify,
+
+    /// Additionally replaces parts of transitivity chains by shorter proofs that the e-graph already knows, taking the longest shortcut first.
+    /// This is similar to the explanation-length optimization of egg.
+    /// The result is never longer than the one of [ProofMinimization::Simplify].
+    GreedyShortest,
+}
+
+/// The result of [EGraph::minimize_proof].
+#[derive(Clone, Debug)]
+pub struct MinimizedProof {
+    pub proof: ProvenEq,
+
+    /// The number of steps of the original proof, see [ProvenEqRaw::step_count].
+    pub steps_before: usize,
+
+    /// The number of steps of the minimized proof.
+    pub steps_after: usize,
+}
+
+impl ProvenEqRaw {
+    /// The number of distinct steps of this proof. Sub-proofs that are used multiple times are only counted once.
+    pub fn step_count(&self) -> usize {
+        let mut seen: HashSet<*const ProvenEqRaw> = HashSet::default();
+        let mut stack: Vec<&ProvenEqRaw> = vec![self];
+        while let Some(x) = stack.pop() {
+            if seen.insert(x as *const ProvenEqRaw) {
+                stack.extend(x.subproofs().into_iter().map(|p| &**p));
+            }
+        }
+        seen.len()
+    }
+}
+
+impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// Shortens a proof, e.g. one returned by [EGraph::explain_equivalence].
+    ///
+    /// The minimized proof shows the same equation, up to renaming of its slots, and never has more steps than the original.
+    pub fn minimize_proof(&self, proof: &ProvenEq, mode: ProofMinimization) -> MinimizedProof {
+        let mut out = Minimizer::new(self, ProofMinimization::Simplify).minimize(proof);
+
+        // shortcuts from the registry may share fewer steps with the rest of the proof than the steps they replace,
+        // so greedy shortcutting doesn't always win.
+        if mode == ProofMinimization::GreedyShortest {
+            let greedy = Minimizer::new(self, mode).minimize(proof);
+            if greedy.step_count() < out.step_count() {
+                out = greedy;
+            }
+        }
+
+        MinimizedProof {
+            steps_before: proof.step_count(),
+            steps_after: out.step_count(),
+            proof: out,
+        }
+    }
+}
+
+struct Minimizer<'a, L: Language, N: Analysis<L>> {
+    eg: &'a EGraph<L, N>,
+    mode: ProofMinimization,
+
+    // the new proofs are registered here instead of in the e-graph,
+    // as the registry of the e-graph would return its older (and possibly longer) proofs for the same equations.
+    reg: ProofRegistry,
+
+    // the minimized version of each visited proof.
+    done: HashMap<*const ProvenEqRaw, ProvenEq>,
+
+    // cached step counts.
+    counts: HashMap<*const ProvenEqRaw, usize>,
+}
+
+impl<'a, L: Language, N: Analysis<L>> Minimizer<'a, L, N> {
+    fn new(eg: &'a EGraph<L, N>, mode: ProofMinimization) -> Self {
+        Self {
+            eg,
+            mode,
+            reg: ProofRegistry::default(),
+            done: HashMap::default(),
+            counts: HashMap::default(),
+        }
+    }
+
+    fn minimize(&mut self, p: &ProvenEq) -> ProvenEq {
+        let ptr = &**p as *const ProvenEqRaw;
+        if let Some(x) = self.done.get(&ptr) {
+            return x.clone();
+        }
+
+        let out = match p.proof() {
+            Proof::Symmetry(_) | Proof::Transitivity(_) => {
+                // Rebuilding the chain with `prove_transitivity` can lose associations of redundant slots
+                // that the original proof chose deliberately. Then we only minimize the sub-proofs.
+                let out = self.minimize_chain(p);
+                if same_equation(&out, p) {
+                    out
+                } else {
+                    self.minimize_children(p)
+                }
+            }
+            _ => self.minimize_children(p),
+        };
+
+        let out = if self.count(&out) <= self.count(p) {
+            out
+        } else {
+            p.clone()
+        };
+        self.done.insert(ptr, out.clone());
+        out
+    }
+
+    // minimizes the sub-proofs of `p`, but keeps its last step.
+    fn minimize_children(&mut self, p: &ProvenEq) -> ProvenEq {
+        let eq = p.equ();
+        match p.proof() {
+            Proof::Explicit(_) | Proof::Reflexivity(_) => p.clone(),
+            Proof::Symmetry(SymmetryProof(a)) => {
+                let a2 = self.minimize(a);
+                if Arc::ptr_eq(a, &a2) {
+                    return p.clone();
+                }
+                SymmetryProof(a2).check(&eq, &self.reg)
+            }
+            Proof::Transitivity(TransitivityProof(a, b)) => {
+                let a2 = self.minimize(a);
+                let b2 = self.minimize(b);
+                if Arc::ptr_eq(a, &a2) && Arc::ptr_eq(b, &b2) {
+                    return p.clone();
+                }
+                TransitivityProof(a2, b2).check(&eq, &self.reg)
+            }
+            Proof::Congruence(CongruenceProof(cs)) => {
+                let cs2: Vec<ProvenEq> = cs.iter().map(|c| self.minimize(c)).collect();
+                if cs2.iter().zip(cs).all(|(a, b)| Arc::ptr_eq(a, b)) {
+                    return p.clone();
+                }
+                CongruenceProof(cs2).check_in(&eq, self.eg, &self.reg)
+            }
+        }
+    }
+
+    fn minimize_chain(&mut self, p: &ProvenEq) -> ProvenEq {
+        // flatten `p` into a chain of steps, pushing symmetries down to the steps.
+        let mut steps = Vec::new();
+        let mut stack = vec![(p.clone(), false)];
+        while let Some((x, flipped)) = stack.pop() {
+            match x.proof() {
+                Proof::Transitivity(TransitivityProof(a, b)) => {
+                    // the steps are popped in reverse order.
+                    if flipped {
+                        stack.push((a.clone(), true));
+                        stack.push((b.clone(), true));
+                    } else {
+                        stack.push((b.clone(), false));
+                        stack.push((a.clone(), false));
+                    }
+                }
+                Proof::Symmetry(SymmetryProof(a)) => stack.push((a.clone(), !flipped)),
+                Proof::Reflexivity(_) => {}
+                _ => {
+                    let x = self.minimize(&x);
+                    steps.push(if flipped {
+                        prove_symmetry(x, &self.reg)
+                    } else {
+                        x
+                    });
+                }
+            }
+        }
+
+        // `terms[i]` is the term before the `i`-th step, written like `prove_transitivity` would name its slots.
+        // Whenever the chain returns to an earlier term, the detour in between is dropped.
+        let mut terms = vec![p.l.clone()];
+        let mut kept: Vec<ProvenEq> = Vec::new();
+        for s in steps {
+            let theta = match_app_id(&s.l, terms.last().unwrap());
+            let t = s.r.apply_slotmap_fresh(&theta);
+            if let Some(i) = terms.iter().position(|x| *x == t) {
+                terms.truncate(i + 1);
+                kept.truncate(i);
+            } else {
+                terms.push(t);
+                kept.push(s);
+            }
+        }
+
+        if self.mode == ProofMinimization::GreedyShortest {
+            kept = self.shortcut(&terms, kept);
+        }
+
+        let mut kept = kept.into_iter();
+        match kept.next() {
+            None => prove_reflexivity(&p.l, &self.reg),
+            Some(first) => kept.fold(first, |acc, s| prove_transitivity(acc, s, &self.reg)),
+        }
+    }
+
+    // replaces parts of the chain by shorter proofs from the registry of the e-graph, taking the longest one first.
+    fn shortcut(&mut self, terms: &[AppliedId], steps: Vec<ProvenEq>) -> Vec<ProvenEq> {
+        // `prefix[k]` is the number of steps of `steps[..k]`.
+        let mut prefix = vec![0];
+        for s in &steps {
+            let c = self.count(s);
+            prefix.push(prefix.last().unwrap() + c);
+        }
+
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < steps.len() {
+            let mut next = (i + 1, steps[i].clone());
+            for j in (i + 1..=steps.len()).rev() {
+                if let Some(q) = self.known_proof(&terms[i], &terms[j]) {
+                    if self.count(&q) < prefix[j] - prefix[i] {
+                        next = (j, q);
+                        break;
+                    }
+                }
+            }
+            out.push(next.1);
+            i = next.0;
+        }
+        out
+    }
+
+    // the shorter of the registered proofs of `a = b` and `b = a`.
+    fn known_proof(&mut self, a: &AppliedId, b: &AppliedId) -> Option<ProvenEq> {
+        let reg = &self.eg.proof_registry;
+        let forward = reg.get(&Equation {
+            l: a.clone(),
+            r: b.clone(),
+        });
+        let backward = reg.get(&Equation {
+            l: b.clone(),
+            r: a.clone(),
+        });
+        match (forward, backward) {
+            (Some(f), Some(b)) if self.count(&b) + 1 < self.count(&f) => {
+                Some(prove_symmetry(b, &self.reg))
+            }
+            (Some(f), _) => Some(f),
+            (None, Some(b)) => Some(prove_symmetry(b, &self.reg)),
+            (None, None) => None,
+        }
+    }
+
+    fn count(&mut self, p: &ProvenEq) -> usize {
+        let ptr = &**p as *const ProvenEqRaw;
+        if let Some(c) = self.counts.get(&ptr) {
+            return *c;
+        }
+        let c = p.step_count();
+        self.counts.insert(ptr, c);
+        c
+    }
+}
+
+// whether `a` and `b` are the same equation, up to a bijective renaming of their slots.
+fn same_equation(a: &Equation, b: &Equation) -> bool {
+    let theta = |x: &AppliedId, y: &AppliedId| {
+        (x.id == y.id && x.m.keys_vec() == y.m.keys_vec()).then(|| x.m.inverse().compose(&y.m))
+    };
+    let (Some(l), Some(r)) = (theta(&a.l, &b.l), theta(&a.r, &b.r)) else {
+        return false;
+    };
+    l.try_union(&r).is_some_and(|t| t.is_bijection())
+}
@@ -15,6 +15,26 @@ mod front;
 #[cfg(feature = "explanations")]
 pub use front::*;
 
+#[cfg(feature = "explanations")]
+mod export;
+#[cfg(feature = "explanations")]
+pub use export::*;
+
+#[cfg(feature = "explanations")]
+mod checker;
+#[cfg(feature = "explanations")]
+pub use checker::*;
+
+#[cfg(feature = "explanations")]
+mod minimize;
+#[cfg(feature = "explanations")]
+pub use minimize::*;
+
+#[cfg(all(feature = "explanations", feature = "serialize"))]
+mod json;
+#[cfg(all(feature = "explanations", feature = "serialize"))]
+pub use json::*;
+
 #[cfg(feature = "explanations")]
 mod registry;
 #[cfg(feature = "explanations")]
@@ -35,19 +55,105 @@ pub use mock::*;
 
 #[cfg(feature = "explanations")]
 impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// Explains why `t1` and `t2` are equal.
+    ///
+    /// Panics if they are not equal or if explanations are disabled, see [EGraph::try_explain_equivalence].
     #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
     pub fn explain_equivalence(&mut self, t1: RecExpr<L>, t2: RecExpr<L>) -> ProvenEq {
+        match self.try_explain_equivalence(t1, t2) {
+            Ok(p) => p,
+            Err(ExplainError::Disabled) => panic!("Explanations are disabled for this e-graph"),
+            Err(_) => panic!("Can't explain an equivalence that does not hold!"),
+        }
+    }
+
+    /// Explains why `t1` and `t2` are equal, or fails if they aren't.
+    ///
+    /// Both terms are added syntactically, as proofs can only talk about terms that are in the e-graph.
+    pub fn try_explain_equivalence(
+        &mut self,
+        t1: RecExpr<L>,
+        t2: RecExpr<L>,
+    ) -> Result<ProvenEq, ExplainError> {
+        if !self.explanations_enabled() {
+            return Err(ExplainError::Disabled);
+        }
+
         let i1 = self.add_syn_expr(t1);
         let i2 = self.add_syn_expr(t2);
+        self.prove_equivalence(&i1, &i2)
+            .ok_or(ExplainError::NotEquivalent(i1.id, i2.id))
+    }
 
-        if !self.eq(&i1, &i2) {
-            panic!("Can't explain an equivalence that does not hold!");
+    /// Explains why the e-classes `i1` and `i2` are equal, or returns `None` if they aren't.
+    ///
+    /// The proof talks about the syntactic terms of `i1` and `i2`, see [EGraph::get_syn_expr].
+    /// In contrast to [EGraph::explain_equivalence], this doesn't add anything to the e-graph.
+    ///
+    /// Like [EGraph::explain_redundancy] and [EGraph::explain_symmetry], this also returns `None` if explanations are disabled, see [EGraph::with_explanations].
+    pub fn explain_equivalence_ids(&self, i1: &AppliedId, i2: &AppliedId) -> Option<ProvenEq> {
+        if !self.explanations_enabled() {
+            return None;
         }
+        let i1 = self.synify_app_id(i1.clone());
+        let i2 = self.synify_app_id(i2.clone());
+        self.prove_equivalence(&i1, &i2)
+    }
 
-        let pai1 = self.proven_find_applied_id(&i1);
+    /// Explains why the slot `s` of `i` is redundant, i.e. why the e-class doesn't depend on it.
+    ///
+    /// The proof shows `c(..., s, ...) = c(..., s', ...)` for a fresh slot `s'`, where `c` is the syntactic term of `i`.
+    /// Returns `None` if `s` is not a redundant argument of `i`, or if explanations are disabled.
+    pub fn explain_redundancy(&self, i: &AppliedId, s: Slot) -> Option<ProvenEq> {
+        if !self.explanations_enabled() {
+            return None;
+        }
+        let l = self.synify_app_id(i.clone());
+        if !l.slots().contains(&s) {
+            return None;
+        }
+        let mut m = SlotMap::identity(&l.slots());
+        m.insert(s, Slot::fresh());
+        let r = l.apply_slotmap(&m);
+        self.prove_equivalence(&l, &r)
+    }
+
+    /// Explains why the e-class `i` is symmetric under `perm`, which permutes some of the arguments of `i`.
+    ///
+    /// The proof shows `c(x_1, ..., x_n) = c(perm(x_1), ..., perm(x_n))`, where `c` is the syntactic term of `i`.
+    /// Returns `None` if `perm` is not a permutation of arguments of `i`, if it's no symmetry of `i`, or if explanations are disabled.
+    pub fn explain_symmetry(&self, i: &AppliedId, perm: &SlotMap) -> Option<ProvenEq> {
+        if !self.explanations_enabled() {
+            return None;
+        }
+        let l = self.synify_app_id(i.clone());
+        if !perm.is_perm() || !perm.keys().is_subset(&l.slots()) {
+            return None;
+        }
+        let mut m = SlotMap::identity(&l.slots());
+        for (x, y) in perm.iter() {
+            m.insert(x, y);
+        }
+        let r = l.apply_slotmap(&m);
+        self.prove_equivalence(&l, &r)
+    }
+
+    // Proves `i1 = i2` from the proofs stored in the unionfind, where `i1` and `i2` are "syn" AppliedIds.
+    // Returns `None` if they are not equal. Explanations have to be enabled.
+    pub(crate) fn prove_equivalence(&self, i1: &AppliedId, i2: &AppliedId) -> Option<ProvenEq> {
+        assert!(
+            self.explanations_enabled(),
+            "Explanations are disabled for this e-graph"
+        );
+
+        if !self.eq(i1, i2) {
+            return None;
+        }
+
+        let pai1 = self.proven_find_applied_id(i1);
         let ProvenAppliedId { elem: l1, proof: _ } = &pai1;
 
-        let pai2 = self.proven_find_applied_id(&i2);
+        let pai2 = self.proven_find_applied_id(i2);
         let ProvenAppliedId {
             elem: l2,
             proof: prf2,
@@ -65,15 +171,29 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             proof: prf1,
         } = self.chain_pai_pp(&pai1, symmetry_prf);
 
-        let prf2 = self.prove_symmetry(prf2.clone());
+        let prf2 = self.prove_symmetry(prf2.clone()?);
 
-        let final_eq = Equation { l: i1, r: i2 };
-        let p = TransitivityProof(prf1, prf2.clone()).check(&final_eq, &self.proof_registry);
+        let final_eq = Equation {
+            l: i1.clone(),
+            r: i2.clone(),
+        };
+        let p = TransitivityProof(prf1?, prf2.clone()).check(&final_eq, &self.proof_registry);
 
         if CHECKS {
             assert_proves_equation(&p, &final_eq);
         }
 
-        p
+        Some(p)
     }
 }
+
+/// The error returned by [EGraph::try_explain_equivalence].
+#[cfg(feature = "explanations")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplainError {
+    /// The syntactic e-classes of the two terms are not equal.
+    NotEquivalent(Id, Id),
+
+    /// The e-graph doesn't record explanations, see [EGraph::with_explanations].
+    Disabled,
+}
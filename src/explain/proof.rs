@@ -8,8 +8,12 @@ pub struct Equation {
     pub r: AppliedId,
 }
 
+/// An axiom with its justification.
+///
+/// For applications of rewrite rules, the second field is the substitution the rule was instantiated with.
+/// Its slot names agree with the equation of the proof.
 #[derive(Clone, Debug)]
-pub struct ExplicitProof(pub Option<String>);
+pub struct ExplicitProof(pub Option<String>, pub Option<Subst>);
 #[derive(Clone, Debug)]
 pub struct ReflexivityProof;
 #[derive(Clone, Debug)]
@@ -108,59 +112,7 @@ impl TransitivityProof {
     pub(crate) fn check(&self, eq: &Equation, reg: &ProofRegistry) -> ProvenEq {
         let TransitivityProof(eq1, eq2) = self;
 
-        let mut theta1 = {
-            // eq1.l*theta1 == eq.l
-            // -> theta1 == eq1.l^-1 * eq.l
-            eq1.l.m.inverse().compose_partial(&eq.l.m)
-        };
-        let mut theta2 = {
-            // eq2.r*theta2 == eq.r
-            // -> theta2 == eq2.r^-1 * eq.r
-            eq2.r.m.inverse().compose_partial(&eq.r.m)
-        };
-
-        let recompute_theta1 = |theta1: &mut SlotMap, theta2: &SlotMap| {
-            // eq1.r*theta1 == eq2.l*theta2
-            // -> theta1 == eq1.r^-1 * eq2.l * theta2
-            *theta1 = theta1
-                .try_union(
-                    &eq1.r
-                        .m
-                        .inverse()
-                        .compose_partial(&eq2.l.m)
-                        .compose_partial(theta2),
-                )
-                .unwrap();
-        };
-
-        let recompute_theta2 = |theta1: &SlotMap, theta2: &mut SlotMap| {
-            // eq1.r*theta1 == eq2.l*theta2
-            // -> theta2 == eq2.l^-1 * eq1.r * theta2
-            *theta2 = theta2
-                .try_union(
-                    &eq2.l
-                        .m
-                        .inverse()
-                        .compose_partial(&eq1.r.m)
-                        .compose_partial(theta1),
-                )
-                .unwrap();
-        };
-
-        recompute_theta1(&mut theta1, &theta2);
-        recompute_theta2(&theta1, &mut theta2);
-
-        for x in eq1.slots() {
-            if !theta1.contains_key(x) {
-                theta1.insert(x, Slot::fresh());
-            }
-        }
-        recompute_theta2(&theta1, &mut theta2);
-        for x in eq2.slots() {
-            if !theta2.contains_key(x) {
-                theta2.insert(x, Slot::fresh());
-            }
-        }
+        let (theta1, theta2) = transitivity_renamings(eq, eq1, eq2);
 
         let renamed_eq1 = eq1.apply_slotmap(&theta1);
         let renamed_eq2 = eq2.apply_slotmap(&theta2);
@@ -175,6 +127,70 @@ impl TransitivityProof {
     }
 }
 
+// Returns the renamings (theta1, theta2), s.t. eq1*theta1 and eq2*theta2 chain up to `eq`.
+// Slots that only occur in the middle of the chain are renamed to fresh slots.
+pub(crate) fn transitivity_renamings(
+    eq: &Equation,
+    eq1: &Equation,
+    eq2: &Equation,
+) -> (SlotMap, SlotMap) {
+    let mut theta1 = {
+        // eq1.l*theta1 == eq.l
+        // -> theta1 == eq1.l^-1 * eq.l
+        eq1.l.m.inverse().compose_partial(&eq.l.m)
+    };
+    let mut theta2 = {
+        // eq2.r*theta2 == eq.r
+        // -> theta2 == eq2.r^-1 * eq.r
+        eq2.r.m.inverse().compose_partial(&eq.r.m)
+    };
+
+    let recompute_theta1 = |theta1: &mut SlotMap, theta2: &SlotMap| {
+        // eq1.r*theta1 == eq2.l*theta2
+        // -> theta1 == eq1.r^-1 * eq2.l * theta2
+        *theta1 = theta1
+            .try_union(
+                &eq1.r
+                    .m
+                    .inverse()
+                    .compose_partial(&eq2.l.m)
+                    .compose_partial(theta2),
+            )
+            .unwrap();
+    };
+
+    let recompute_theta2 = |theta1: &SlotMap, theta2: &mut SlotMap| {
+        // eq1.r*theta1 == eq2.l*theta2
+        // -> theta2 == eq2.l^-1 * eq1.r * theta2
+        *theta2 = theta2
+            .try_union(
+                &eq2.l
+                    .m
+                    .inverse()
+                    .compose_partial(&eq1.r.m)
+                    .compose_partial(theta1),
+            )
+            .unwrap();
+    };
+
+    recompute_theta1(&mut theta1, &theta2);
+    recompute_theta2(&theta1, &mut theta2);
+
+    for x in eq1.slots() {
+        if !theta1.contains_key(x) {
+            theta1.insert(x, Slot::fresh());
+        }
+    }
+    recompute_theta2(&theta1, &mut theta2);
+    for x in eq2.slots() {
+        if !theta2.contains_key(x) {
+            theta2.insert(x, Slot::fresh());
+        }
+    }
+
+    (theta1, theta2)
+}
+
 // replaces 'private' slots with enumerated slot-names, like a shape.
 pub(crate) fn alpha_normalize<L: Language>(n: &L) -> L {
     let (sh, bij) = n.weak_shape();
@@ -187,10 +203,20 @@ pub(crate) fn alpha_normalize<L: Language>(n: &L) -> L {
 
 impl CongruenceProof {
     pub fn check<L: Language, N: Analysis<L>>(&self, eq: &Equation, eg: &EGraph<L, N>) -> ProvenEq {
+        self.check_in(eq, eg, &eg.proof_registry)
+    }
+
+    // like `check`, but registers the proof in `reg` instead of the registry of `eg`.
+    pub(crate) fn check_in<L: Language, N: Analysis<L>>(
+        &self,
+        eq: &Equation,
+        eg: &EGraph<L, N>,
+        reg: &ProofRegistry,
+    ) -> ProvenEq {
         let CongruenceProof(child_proofs) = self;
 
-        let l = alpha_normalize(&eg.get_syn_node(&eq.l));
-        let r = alpha_normalize(&eg.get_syn_node(&eq.r));
+        let l = alpha_normalize(&eg.syn_node(&eq.l));
+        let r = alpha_normalize(&eg.syn_node(&eq.r));
 
         let null_l = nullify_app_ids(&l);
         let null_r = nullify_app_ids(&r);
@@ -214,8 +240,7 @@ impl CongruenceProof {
 
         let eq = eq.clone();
         let proof = Proof::Congruence(self.clone());
-        eg.proof_registry
-            .insert(Arc::new(ProvenEqRaw { eq, proof }))
+        reg.insert(Arc::new(ProvenEqRaw { eq, proof }))
     }
 }
 
@@ -3,7 +3,39 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 #[derive(Clone, Default, Debug)]
-pub(crate) struct ProofRegistry(Rc<RefCell<HashMap<Equation, ProvenEq>>>);
+pub(crate) struct ProofRegistry {
+    inner: Rc<RefCell<RegistryInner>>,
+
+    // set for e-graphs that don't record explanations, see `EGraph::with_explanations`.
+    // Then proofs are only stand-ins, and nothing is stored.
+    disabled: bool,
+}
+
+#[derive(Default, Debug)]
+struct RegistryInner {
+    map: HashMap<Equation, ProvenEq>,
+
+    // see `EGraph::set_proof_registry_cap`.
+    cap: Option<usize>,
+
+    // the unreferenced entries are collected once the registry has more than `limit` entries.
+    // If most entries are still in use, collecting doesn't help, so `limit` grows with the registry.
+    limit: usize,
+}
+
+/// Statistics about the proofs cached by an [EGraph], see [EGraph::proof_registry_stats].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProofRegistryStats {
+    /// The number of cached equations.
+    pub equations: usize,
+
+    /// The number of distinct proof steps that are kept alive by the cached equations.
+    pub steps: usize,
+
+    /// A lower bound for the memory used by the cached equations and their steps, in bytes.
+    /// Heap-allocated slot maps are not counted.
+    pub approx_bytes: usize,
+}
 
 fn normalize_eq(eq: &Equation) -> Equation {
     let mut theta = SlotMap::new();
@@ -19,19 +51,143 @@ fn normalize_eq(eq: &Equation) -> Equation {
 }
 
 impl ProofRegistry {
+    pub(crate) fn disabled() -> Self {
+        ProofRegistry {
+            inner: Default::default(),
+            disabled: true,
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.disabled
+    }
+
     // will not actually insert if the registry already has a proof for it.
     pub(crate) fn insert(&self, peq: ProvenEq) -> ProvenEq {
+        if self.disabled {
+            return peq;
+        }
+
         let eq = normalize_eq(&peq.equ());
 
-        let mut handle = self.0.borrow_mut();
+        let mut handle = self.inner.borrow_mut();
 
-        if let Some(x) = handle.get(&eq) {
+        if let Some(x) = handle.map.get(&eq) {
             return x.clone();
         }
 
         // TODO add special cases for proofs of the form x=x, and symmetry.
 
-        handle.insert(eq, peq.clone());
+        handle.map.insert(eq, peq.clone());
+        if let Some(cap) = handle.cap {
+            if handle.map.len() > handle.limit.max(cap) {
+                handle.collect_unreferenced(cap);
+            }
+        }
         peq
     }
+
+    // the registered proof of `eq`, up to renaming.
+    pub(crate) fn get(&self, eq: &Equation) -> Option<ProvenEq> {
+        self.inner.borrow().map.get(&normalize_eq(eq)).cloned()
+    }
+
+    pub(crate) fn set_cap(&self, cap: Option<usize>) {
+        let mut handle = self.inner.borrow_mut();
+        handle.cap = cap;
+        handle.limit = 0;
+        if let Some(cap) = cap {
+            if handle.map.len() > cap {
+                handle.collect_unreferenced(cap);
+            }
+        }
+    }
+
+    // keeps the entries whose proofs satisfy `f`, returns the number of removed entries.
+    pub(crate) fn retain(&self, mut f: impl FnMut(&ProvenEq) -> bool) -> usize {
+        let map = &mut self.inner.borrow_mut().map;
+        let len = map.len();
+        map.retain(|_, p| f(p));
+        len - map.len()
+    }
+
+    pub(crate) fn stats(&self) -> ProofRegistryStats {
+        let handle = self.inner.borrow();
+        let steps = reachable_steps(handle.map.values()).len();
+        ProofRegistryStats {
+            equations: handle.map.len(),
+            steps,
+            approx_bytes: handle.map.len() * size_of::<(Equation, ProvenEq)>()
+                + steps * size_of::<ProvenEqRaw>(),
+        }
+    }
+}
+
+impl RegistryInner {
+    // A garbage collection of the equations whose proofs are not used outside of the registry.
+    // These are only cached to share steps between future proofs.
+    // The entries are not ranked by value: all unreferenced ones are dropped, and all others are kept,
+    // as dropping a proof that is still in use doesn't free any memory.
+    fn collect_unreferenced(&mut self, cap: usize) {
+        // dropping a proof can make its sub-proofs unused, so we repeat until nothing changes.
+        loop {
+            let len = self.map.len();
+            self.map.retain(|_, p| Arc::strong_count(p) > 1);
+            if self.map.len() == len {
+                break;
+            }
+        }
+        self.limit = cap.max(2 * self.map.len());
+    }
+}
+
+// the pointers to all proof steps reachable from `roots`.
+fn reachable_steps<'a>(roots: impl Iterator<Item = &'a ProvenEq>) -> HashSet<*const ProvenEqRaw> {
+    let mut seen = HashSet::default();
+    let mut stack: Vec<&ProvenEqRaw> = roots.map(|p| &**p).collect();
+    while let Some(x) = stack.pop() {
+        if seen.insert(x as *const ProvenEqRaw) {
+            stack.extend(x.subproofs().into_iter().map(|p| &**p));
+        }
+    }
+    seen
+}
+
+impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// Drops the cached proofs that are no longer reachable from the unionfind or the symmetry groups of the e-graph.
+    ///
+    /// The e-graph caches a proof for every equation it ever proved, so that later proofs can share steps.
+    /// In long runs, most of them become unreachable, and this frees them.
+    /// Returns the number of dropped equations.
+    pub fn collect_proofs(&mut self) -> usize {
+        let mut roots = self.unionfind_proofs();
+        for (i, c) in &self.classes {
+            if self.is_alive(*i) {
+                roots.extend(
+                    c.group
+                        .stored_perms_iter()
+                        .filter_map(|p| Some(p.proof.as_ref()?.0.clone())),
+                );
+            }
+        }
+
+        let live = reachable_steps(roots.iter());
+        self.proof_registry
+            .retain(|p| live.contains(&(&**p as *const ProvenEqRaw)))
+    }
+
+    /// Limits the number of cached proofs, see [EGraph::collect_proofs].
+    ///
+    /// Whenever the cache grows beyond `cap` equations, all equations whose proofs are not used anywhere else are dropped,
+    /// no matter how often they were reused before. This is a garbage collection of unreferenced proofs, not a ranking:
+    /// the remaining equations are still in use, so dropping them wouldn't free memory.
+    /// Hence the cache can stay above `cap` until they become unused.
+    pub fn set_proof_registry_cap(&mut self, cap: Option<usize>) {
+        self.proof_registry.set_cap(cap);
+    }
+
+    /// Returns statistics about the cached proofs.
+    pub fn proof_registry_stats(&self) -> ProofRegistryStats {
+        self.proof_registry.stats()
+    }
 }
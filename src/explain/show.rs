@@ -5,7 +5,7 @@ type ShowMap = HashMap<*const ProvenEqRaw, (usize, String)>;
 impl ProvenEqRaw {
     /// Prints the proof steps.
     pub fn to_string<L: Language, N: Analysis<L>>(&self, eg: &EGraph<L, N>) -> String {
-        self.show_impl(&|i| eg.get_syn_expr(i).to_string())
+        self.show_impl(&|i| eg.syn_expr(i).to_string())
     }
 
     // internals:
@@ -23,9 +23,9 @@ impl ProvenEqRaw {
         out
     }
 
-    fn subproofs(&self) -> Vec<&ProvenEq> {
+    pub(crate) fn subproofs(&self) -> Vec<&ProvenEq> {
         match self.proof() {
-            Proof::Explicit(ExplicitProof(_)) => vec![],
+            Proof::Explicit(ExplicitProof(_, _)) => vec![],
             Proof::Reflexivity(ReflexivityProof) => vec![],
             Proof::Symmetry(SymmetryProof(x)) => vec![x],
             Proof::Transitivity(TransitivityProof(x1, x2)) => vec![x1, x2],
@@ -48,7 +48,7 @@ impl ProvenEqRaw {
                 }
             }
             let prf_string = match x.proof() {
-                Proof::Explicit(ExplicitProof(j)) => format!("{j:?}"),
+                Proof::Explicit(ExplicitProof(j, _)) => format!("{j:?}"),
                 Proof::Reflexivity(ReflexivityProof) => format!("refl"),
                 Proof::Symmetry(SymmetryProof(_)) => format!("symmetry({})", ids[0]),
                 Proof::Transitivity(TransitivityProof(_, _)) => {
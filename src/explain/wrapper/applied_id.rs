@@ -4,17 +4,18 @@ use crate::*;
 pub(crate) struct ProvenAppliedId {
     pub elem: AppliedId,
 
+    // is `None` if explanations are disabled.
     #[cfg(feature = "explanations")]
-    pub proof: ProvenEq,
+    pub proof: Option<ProvenEq>,
 }
 
 impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     pub(crate) fn check_pai(&self, #[allow(unused)] pai: &ProvenAppliedId) {
         #[cfg(feature = "explanations")]
-        {
-            assert_eq!(pai.proof.r.id, pai.elem.id);
-            self.check_syn_applied_id(&pai.proof.l);
-            self.check_syn_applied_id(&pai.proof.r);
+        if let Some(proof) = &pai.proof {
+            assert_eq!(proof.r.id, pai.elem.id);
+            self.check_syn_applied_id(&proof.l);
+            self.check_syn_applied_id(&proof.r);
         }
     }
 
@@ -37,11 +38,11 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             elem: next.elem.apply_slotmap(&start.elem.m),
 
             #[cfg(feature = "explanations")]
-            proof: prove_transitivity(
-                start.proof.clone(),
-                next.proof.clone(),
-                &self.proof_registry,
-            ),
+            proof: start
+                .proof
+                .clone()
+                .zip(next.proof.clone())
+                .map(|(x, y)| prove_transitivity(x, y, &self.proof_registry)),
         }
     }
 
@@ -50,11 +51,11 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             elem: app_id.clone(),
 
             #[cfg(feature = "explanations")]
-            proof: {
+            proof: self.explanations_enabled().then(|| {
                 // should this already be synified before calling this?
                 let syn = self.synify_app_id(app_id.clone());
                 prove_reflexivity(&syn, &self.proof_registry)
-            },
+            }),
         }
     }
 
@@ -63,7 +64,11 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             elem: self.mk_sem_applied_id(pai.elem.id, pp.elem.compose(&pai.elem.m)),
 
             #[cfg(feature = "explanations")]
-            proof: self.prove_transitivity(pai.proof.clone(), pp.proof.clone()),
+            proof: pai
+                .proof
+                .clone()
+                .zip(pp.proof())
+                .map(|(x, y)| self.prove_transitivity(x, y)),
         }
     }
 
@@ -71,13 +76,17 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     pub(crate) fn chain_pai_eq(
         &self,
         pai: &ProvenAppliedId,
-        #[allow(unused)] peq: ProvenEq,
+        #[allow(unused)] peq: Option<ProvenEq>,
     ) -> ProvenAppliedId {
         ProvenAppliedId {
             elem: pai.elem.clone(),
 
             #[cfg(feature = "explanations")]
-            proof: self.prove_transitivity(pai.proof.clone(), peq),
+            proof: pai
+                .proof
+                .clone()
+                .zip(peq)
+                .map(|(x, y)| self.prove_transitivity(x, y)),
         }
     }
 }
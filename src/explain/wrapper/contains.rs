@@ -17,9 +17,10 @@ pub(crate) struct ProvenContains<L> {
 }
 
 impl<L: Language> ProvenContains<L> {
+    // is `None` if explanations are disabled.
     #[cfg(feature = "explanations")]
-    pub fn src_id(&self) -> Id {
-        self.pai.proof.l.id
+    pub fn src_id(&self) -> Option<Id> {
+        self.pai.proof.as_ref().map(|p| p.l.id)
     }
 
     pub fn target_id(&self) -> Id {
@@ -33,21 +34,21 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         self.check_pn(&pc.node);
 
         #[cfg(feature = "explanations")]
-        {
+        if let (Some(proof), Some(proofs)) = (&pc.pai.proof, &pc.node.proofs) {
             let a: Vec<Id> = self
-                .get_syn_node(&self.mk_syn_identity_applied_id(pc.pai.proof.l.id))
+                .syn_node(&self.mk_syn_identity_applied_id(proof.l.id))
                 .applied_id_occurrences()
                 .iter()
                 .map(|x| x.id)
                 .collect();
-            let b: Vec<Id> = pc.node.proofs.iter().map(|eq| eq.l.id).collect();
+            let b: Vec<Id> = proofs.iter().map(|eq| eq.l.id).collect();
             assert_eq!(a, b);
         }
     }
 
     pub(crate) fn refl_pc(&self, i: Id) -> ProvenContains<L> {
         let identity = self.mk_syn_identity_applied_id(i);
-        let n = self.get_syn_node(&identity);
+        let n = self.syn_node(&identity);
 
         ProvenContains {
             node: self.refl_pn(&n),
@@ -60,6 +61,19 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         self.pc_find(&self.refl_pc(i))
     }
 
+    // `node` is an e-node of the e-class `i`, which originates from the e-class `src_id`.
+    pub(crate) fn pc_from_node(&self, src_id: Id, i: Id, node: &L) -> ProvenContains<L> {
+        if self.explanations_enabled() {
+            return self.pc_from_src_id(src_id);
+        }
+
+        let pc = ProvenContains {
+            node: self.refl_pn(node),
+            pai: self.refl_pai(&self.mk_sem_identity_applied_id(i)),
+        };
+        self.pc_find(&pc)
+    }
+
     // "finds" both the node & the id to be "up-to-date".
     pub(crate) fn pc_find(&self, pc: &ProvenContains<L>) -> ProvenContains<L> {
         ProvenContains {
@@ -84,7 +98,11 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     }
 
     #[allow(unused)]
-    pub(crate) fn chain_pc_eq(&self, start: &ProvenContains<L>, eq: ProvenEq) -> ProvenContains<L> {
+    pub(crate) fn chain_pc_eq(
+        &self,
+        start: &ProvenContains<L>,
+        eq: Option<ProvenEq>,
+    ) -> ProvenContains<L> {
         ProvenContains {
             node: start.node.clone(),
             pai: self.chain_pai_eq(&start.pai, eq),
@@ -133,7 +151,7 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         &self,
         a: &ProvenContains<L>,
         b: &ProvenContains<L>,
-    ) -> (AppliedId, AppliedId, ProvenEq) {
+    ) -> (AppliedId, AppliedId, Option<ProvenEq>) {
         if CHECKS {
             self.check_pc(a);
             self.check_pc(b);
@@ -146,34 +164,42 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
             self.check_pc(&b);
         }
 
-        let prf = ghost!({
-            let prf_a = &*a.node.proofs;
-            let prf_b = &*b.node.proofs;
+        let prf = ghost!(self.pc_congruence_proof(&a, &b));
 
-            if CHECKS {
-                assert_eq!(prf_a.len(), prf_b.len());
-            }
+        // a.target -> b.target
+        (a.pai.elem.clone(), b.pai.elem.clone(), prf)
+    }
 
-            let mut vec = Vec::new();
-            for (pa, pb) in prf_a.iter().zip(prf_b.iter()) {
-                let pb_inv = self.prove_symmetry(pb.clone());
-                let pa_to_pb = self.prove_transitivity(pa.clone(), pb_inv);
-                vec.push(pa_to_pb);
-            }
+    // is `None` if explanations are disabled.
+    #[cfg(feature = "explanations")]
+    fn pc_congruence_proof(
+        &self,
+        a: &ProvenContains<L>,
+        b: &ProvenContains<L>,
+    ) -> Option<ProvenEq> {
+        let prf_a = a.node.proofs.as_ref()?;
+        let prf_b = b.node.proofs.as_ref()?;
+
+        if CHECKS {
+            assert_eq!(prf_a.len(), prf_b.len());
+        }
 
-            // a.src -> b.src
-            let prf = self.prove_congruence(a.src_id(), b.src_id(), &vec);
+        let mut vec = Vec::new();
+        for (pa, pb) in prf_a.iter().zip(prf_b.iter()) {
+            let pb_inv = self.prove_symmetry(pb.clone());
+            let pa_to_pb = self.prove_transitivity(pa.clone(), pb_inv);
+            vec.push(pa_to_pb);
+        }
 
-            // a.proof :: a.src -> a.target
-            // b.proof :: b.src -> b.target
+        // a.src -> b.src
+        let prf = self.prove_congruence(a.src_id()?, b.src_id()?, &vec);
 
-            let sym_a = prove_symmetry(a.pai.proof.clone(), &self.proof_registry);
-            let prf = prove_transitivity(sym_a, prf, &self.proof_registry);
-            let prf = prove_transitivity(prf, b.pai.proof.clone(), &self.proof_registry);
-            prf
-        });
+        // a.proof :: a.src -> a.target
+        // b.proof :: b.src -> b.target
 
-        // a.target -> b.target
-        (a.pai.elem.clone(), b.pai.elem.clone(), prf)
+        let sym_a = prove_symmetry(a.pai.proof.clone()?, &self.proof_registry);
+        let prf = prove_transitivity(sym_a, prf, &self.proof_registry);
+        let prf = prove_transitivity(prf, b.pai.proof.clone()?, &self.proof_registry);
+        Some(prf)
     }
 }
@@ -8,8 +8,9 @@ pub(crate) struct ProvenNode<L> {
 
     // These proofs have as 'lhs' the base that is situation dependent.
     // And the 'rhs' is 'elem'.
+    // is `None` if explanations are disabled.
     #[cfg(feature = "explanations")]
-    pub proofs: Vec<ProvenEq>,
+    pub proofs: Option<Vec<ProvenEq>>,
 }
 
 impl<L: Language> PartialEq for ProvenNode<L> {
@@ -31,16 +32,17 @@ impl<L: Language> ProvenNode<L> {
     #[cfg(feature = "explanations")]
     #[allow(unused)]
     pub(crate) fn check_base(&self, base: &L) {
+        let Some(proofs) = &self.proofs else { return };
         let l = base.applied_id_occurrences();
         let r = self.elem.applied_id_occurrences();
-        let n = self.proofs.len();
+        let n = proofs.len();
         assert_eq!(n, l.len());
         assert_eq!(n, r.len());
         for i in 0..n {
             let l = l[i].clone();
             let r = r[i].clone();
             let eq = Equation { l, r };
-            assert_proves_equation(&self.proofs[i], &eq);
+            assert_proves_equation(&proofs[i], &eq);
         }
     }
 
@@ -59,8 +61,7 @@ impl<L: Language> ProvenNode<L> {
 impl<L: Language, N: Analysis<L>> EGraph<L, N> {
     pub(crate) fn check_pn(&self, #[allow(unused)] pn: &ProvenNode<L>) {
         #[cfg(feature = "explanations")]
-        {
-            let a = &pn.proofs;
+        if let Some(a) = &pn.proofs {
             let b = &pn.elem.applied_id_occurrences();
             assert_eq!(a.len(), b.len());
             for (x, y) in a.iter().zip(b.iter()) {
@@ -78,11 +79,13 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
 
     pub(crate) fn refl_pn(&self, start: &L) -> ProvenNode<L> {
         #[cfg(feature = "explanations")]
-        let rfl = start
-            .applied_id_occurrences()
-            .into_iter()
-            .map(|x| self.refl_proof(x.id))
-            .collect();
+        let rfl = self.explanations_enabled().then(|| {
+            start
+                .applied_id_occurrences()
+                .into_iter()
+                .map(|x| self.refl_proof(x.id))
+                .collect()
+        });
         ProvenNode {
             elem: start.clone(),
             #[cfg(feature = "explanations")]
@@ -109,25 +112,23 @@ impl<L: Language, N: Analysis<L>> EGraph<L, N> {
         let mut app_ids_mut: Vec<&mut AppliedId> = pn.elem.applied_id_occurrences_mut();
 
         #[cfg(feature = "explanations")]
-        let proofs_mut: &mut [ProvenEq] = &mut pn.proofs;
+        let mut proofs_mut: Option<&mut [ProvenEq]> = pn.proofs.as_deref_mut();
 
         for i in 0..n {
             let old_app_id: &mut AppliedId = app_ids_mut[i];
-            #[cfg(feature = "explanations")]
-            let old_proof: &mut ProvenEq = &mut proofs_mut[i];
 
             let tmp_pai = ProvenAppliedId {
                 elem: old_app_id.clone(),
                 #[cfg(feature = "explanations")]
-                proof: old_proof.clone(),
+                proof: proofs_mut.as_ref().map(|p| p[i].clone()),
             };
             let pai = f(i, tmp_pai);
 
             *old_app_id = pai.elem;
 
             #[cfg(feature = "explanations")]
-            {
-                *old_proof = pai.proof;
+            if let (Some(p), Some(proof)) = (&mut proofs_mut, pai.proof) {
+                p[i] = proof;
             }
         }
         pn
@@ -29,11 +29,9 @@ impl Permutation for Perm {
 pub(crate) struct ProvenPerm {
     pub elem: Perm,
 
+    // is `None` if explanations are disabled.
     #[cfg(feature = "explanations")]
-    pub proof: ProvenEq,
-
-    #[cfg(feature = "explanations")]
-    pub reg: ProofRegistry,
+    pub proof: Option<(ProvenEq, ProofRegistry)>,
 }
 
 impl PartialEq for ProvenPerm {
@@ -61,20 +59,24 @@ impl Permutation for ProvenPerm {
         }
         #[cfg(feature = "explanations")]
         if CHECKS {
-            assert_eq!(self.proof.l.id, self.proof.r.id);
-            assert_eq!(other.proof.l.id, other.proof.r.id);
-            assert_eq!(self.proof.l.id, other.proof.l.id);
+            if let (Some((p1, _)), Some((p2, _))) = (&self.proof, &other.proof) {
+                assert_eq!(p1.l.id, p1.r.id);
+                assert_eq!(p2.l.id, p2.r.id);
+                assert_eq!(p1.l.id, p2.l.id);
+            }
         }
         // TODO why is this the other way around?
         let map = self.elem.compose(&other.elem);
-        #[cfg(feature = "explanations")]
-        let prf = prove_transitivity(other.proof.clone(), self.proof.clone(), &self.reg);
         let out = ProvenPerm {
             elem: map,
             #[cfg(feature = "explanations")]
-            proof: prf,
-            #[cfg(feature = "explanations")]
-            reg: self.reg.clone(),
+            proof: self
+                .proof
+                .as_ref()
+                .zip(other.proof.as_ref())
+                .map(|((p1, reg), (p2, _))| {
+                    (prove_transitivity(p2.clone(), p1.clone(), reg), reg.clone())
+                }),
         };
         if CHECKS {
             out.check();
@@ -87,14 +89,13 @@ impl Permutation for ProvenPerm {
             self.check();
         }
         let map = self.elem.inverse();
-        #[cfg(feature = "explanations")]
-        let prf = prove_symmetry(self.proof.clone(), &self.reg);
         let out = ProvenPerm {
             elem: map,
             #[cfg(feature = "explanations")]
-            proof: prf,
-            #[cfg(feature = "explanations")]
-            reg: self.reg.clone(),
+            proof: self
+                .proof
+                .as_ref()
+                .map(|(p, reg)| (prove_symmetry(p.clone(), reg), reg.clone())),
         };
         if CHECKS {
             out.check();
@@ -104,26 +105,41 @@ impl Permutation for ProvenPerm {
 }
 
 impl ProvenPerm {
+    pub(crate) fn new(
+        elem: Perm,
+        #[allow(unused)] proof: Option<ProvenEq>,
+        #[allow(unused)] reg: &ProofRegistry,
+    ) -> Self {
+        ProvenPerm {
+            elem,
+            #[cfg(feature = "explanations")]
+            proof: proof.map(|p| (p, reg.clone())),
+        }
+    }
+
+    #[cfg(feature = "explanations")]
+    pub(crate) fn proof(&self) -> Option<ProvenEq> {
+        self.proof.as_ref().map(|(proof, _)| proof.clone())
+    }
+
     pub(crate) fn identity(
         #[allow(unused)] i: Id,
         slots: &SmallHashSet<Slot>,
         #[allow(unused)] syn_slots: &SmallHashSet<Slot>,
-        #[allow(unused)] reg: ProofRegistry,
+        #[allow(unused)] reg: &ProofRegistry,
     ) -> Self {
         let map = Perm::identity(slots);
 
         #[cfg(feature = "explanations")]
-        let identity = SlotMap::identity(syn_slots);
-        #[cfg(feature = "explanations")]
-        let app_id = AppliedId::new(i, identity);
-        #[cfg(feature = "explanations")]
-        let prf = prove_reflexivity(&app_id, &reg);
+        let proof = reg.is_enabled().then(|| {
+            let identity = SlotMap::identity(syn_slots);
+            let app_id = AppliedId::new(i, identity);
+            (prove_reflexivity(&app_id, reg), reg.clone())
+        });
         ProvenPerm {
             elem: map,
             #[cfg(feature = "explanations")]
-            proof: prf,
-            #[cfg(feature = "explanations")]
-            reg,
+            proof,
         }
     }
 
@@ -131,21 +147,21 @@ impl ProvenPerm {
         assert!(self.elem.is_perm());
 
         #[cfg(feature = "explanations")]
-        {
-            let id = self.proof.l.id;
+        if let Some((proof, _)) = &self.proof {
+            let id = proof.l.id;
             let slots = self.elem.keys();
-            let syn_slots = self.proof.l.m.keys();
+            let syn_slots = proof.l.m.keys();
 
-            assert_eq!(id, self.proof.l.id);
-            assert_eq!(id, self.proof.r.id);
-            assert_eq!(&self.proof.l.m.keys(), &syn_slots);
-            assert_eq!(&self.proof.r.m.keys(), &syn_slots);
+            assert_eq!(id, proof.l.id);
+            assert_eq!(id, proof.r.id);
+            assert_eq!(&proof.l.m.keys(), &syn_slots);
+            assert_eq!(&proof.r.m.keys(), &syn_slots);
 
             let eq = Equation {
                 l: AppliedId::new(id, SlotMap::identity(&slots)),
                 r: AppliedId::new(id, self.elem.clone()),
             };
-            assert_proves_equation(&self.proof, &eq);
+            assert_proves_equation(proof, &eq);
         }
     }
 }
@@ -7,6 +7,10 @@ use std::marker::PhantomData;
 /// If you want to use your e-graph analysis in your cost function, then your cost function should hold a reference to the e-graph.
 pub trait CostFunction<L: Language> {
     type Cost: Ord + Clone + Debug;
+
+    /// The cost of `enode`, given the cost `costs` of each child.
+    ///
+    /// The children of `enode` might not be actual e-classes, but numbered by their position, starting with `Id(0)`.
     fn cost<C>(&self, enode: &L, costs: C) -> Self::Cost
     where
         C: Fn(Id) -> Self::Cost;
@@ -22,6 +26,46 @@ pub trait CostFunction<L: Language> {
     }
 }
 
+/// A cost function that can take slots into account, e.g. to penalize terms with many free variables.
+///
+/// In contrast to a [CostFunction], it sees the children as [AppliedId]s, i.e. including the [SlotMap] that describes how the slots are passed into the child e-class,
+/// and it gets the free slots of the term that `enode` is the top of.
+/// The [Extractor] calls `slot_cost` with `enode` written in terms of the slots of this term, i.e. the slots of its e-class, renamed by how the e-class is used.
+/// `costs` returns the cost of the best term of a child e-class under the renaming of the child.
+/// Hence the best term of an e-class can depend on how its slots are renamed, and the [Extractor] chooses it for each renaming, up to the symmetries of the e-class.
+///
+/// Every [CostFunction] is also a [SlotCostFunction], which ignores the slots.
+pub trait SlotCostFunction<L: Language> {
+    type Cost: Ord + Clone + Debug;
+
+    /// Whether the cost of a term stays the same if its slots are renamed, e.g. if it only depends on the number of free slots.
+    /// This lets the [Extractor] choose a single best term per e-class. Every [CostFunction] is renaming invariant.
+    const RENAMING_INVARIANT: bool = false;
+
+    fn slot_cost<C>(&self, enode: &L, slots: &SmallHashSet<Slot>, costs: C) -> Self::Cost
+    where
+        C: Fn(&AppliedId) -> Self::Cost;
+}
+
+impl<L: Language, CF: CostFunction<L>> SlotCostFunction<L> for CF {
+    type Cost = CF::Cost;
+    const RENAMING_INVARIANT: bool = true;
+
+    fn slot_cost<C>(&self, enode: &L, _slots: &SmallHashSet<Slot>, costs: C) -> CF::Cost
+    where
+        C: Fn(&AppliedId) -> CF::Cost,
+    {
+        // The children are numbered by position like in `cost_rec`,
+        // as the same e-class can occur several times with different renamings.
+        let children = enode.applied_id_occurrences();
+        let mut node = enode.clone();
+        for (i, x) in node.applied_id_occurrences_mut().iter_mut().enumerate() {
+            **x = AppliedId::new(Id(i), SlotMap::new());
+        }
+        self.cost(&node, |i| costs(children[i.0]))
+    }
+}
+
 /// The 'default' [CostFunction]. It measures the size of the abstract syntax tree of the corresponding term.
 #[derive(Default)]
 pub struct AstSize;
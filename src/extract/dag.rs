@@ -0,0 +1,281 @@
+use crate::*;
+
+use std::ops::Add;
+
+/// An extractor that minimizes the DAG cost of a term, i.e. it counts shared subterms only once.
+///
+/// The cost of an e-node is the cost that the [CostFunction] assigns to it when all children have the default cost,
+/// and the DAG cost of a term is the sum of the costs of its distinct subterms.
+/// Two uses of the same e-class under different renamings of its slots are different subterms, and are hence counted separately.
+/// Still, only one e-node is chosen per e-class [Id], and all renamings of the e-class use it.
+/// Costs are expected to be non-negative.
+///
+/// The optimal choice of e-nodes is found using branch-and-bound.
+/// As this can take exponential time, the search can be bounded using [DagExtractor::with_step_limit].
+pub struct DagExtractor<L: Language, CF: CostFunction<L>> {
+    // the e-nodes (in class normal-form) that may be chosen for each e-class, ordered by their cost.
+    candidates: HashMap<Id, Vec<(L, CF::Cost)>>,
+
+    // an acyclic choice minimizing the tree cost, used as the initial solution.
+    greedy: HashMap<Id, L>,
+
+    step_limit: Option<usize>,
+}
+
+/// The result of a [DagExtractor].
+#[derive(Debug)]
+pub struct DagExtraction<L: Language, C> {
+    pub term: RecExpr<L>,
+
+    /// The DAG cost of `term`.
+    pub cost: C,
+
+    /// A lower bound for the DAG cost of any term in the e-class.
+    /// If the search completed, this is equal to `cost`.
+    pub lower_bound: C,
+
+    /// Whether `term` is known to be optimal.
+    pub optimal: bool,
+}
+
+struct Search<'a, L: Language, C> {
+    candidates: &'a HashMap<Id, Vec<(L, C)>>,
+    root: AppliedId,
+    steps: usize,
+    step_limit: Option<usize>,
+    best: (HashMap<Id, L>, C),
+    // the smallest lower bound of the branches cut off by the step limit.
+    abandoned: Option<C>,
+}
+
+impl<L: Language, CF: CostFunction<L>> DagExtractor<L, CF>
+where
+    CF::Cost: Default + Add<Output = CF::Cost>,
+{
+    pub fn new<N: Analysis<L>>(eg: &EGraph<L, N>, cost_fn: CF) -> Self {
+        if CHECKS {
+            eg.check();
+        }
+
+        let mut all: HashMap<Id, Vec<(L, CF::Cost)>> = HashMap::default();
+        for id in eg.ids() {
+            let mut v: Vec<(L, CF::Cost)> = eg
+                .sorted_enodes(id)
+                .into_iter()
+                .map(|x| {
+                    let x = eg.class_nf(&x);
+                    let c = cost_fn.cost(&x, |_| CF::Cost::default());
+                    (x, c)
+                })
+                .collect();
+            v.sort_by(|(_, c1), (_, c2)| c1.cmp(c2));
+            all.insert(id, v);
+        }
+
+        // Compute the minimal tree cost of each e-class by fixpoint iteration.
+        // As choices are only changed on strict improvement, the resulting choice is acyclic.
+        let mut tree_cost: HashMap<Id, CF::Cost> = HashMap::default();
+        let mut greedy: HashMap<Id, L> = HashMap::default();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (id, nodes) in &all {
+                for (x, c) in nodes {
+                    let mut total = c.clone();
+                    let mut finite = true;
+                    for child in x.applied_id_occurrences() {
+                        match tree_cost.get(&child.id) {
+                            Some(cc) => total = total + cc.clone(),
+                            None => finite = false,
+                        }
+                    }
+                    if finite && tree_cost.get(id).map(|old| total < *old).unwrap_or(true) {
+                        tree_cost.insert(*id, total);
+                        greedy.insert(*id, x.clone());
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        // Drop e-nodes with children that don't represent any finite term.
+        let candidates = all
+            .into_iter()
+            .filter(|(id, _)| tree_cost.contains_key(id))
+            .map(|(id, nodes)| {
+                let nodes = nodes
+                    .into_iter()
+                    .filter(|(x, _)| {
+                        x.applied_id_occurrences()
+                            .iter()
+                            .all(|c| tree_cost.contains_key(&c.id))
+                    })
+                    .collect();
+                (id, nodes)
+            })
+            .collect();
+
+        Self {
+            candidates,
+            greedy,
+            step_limit: None,
+        }
+    }
+
+    /// Stops the search after visiting `n` partial choices, and returns the best term found so far.
+    pub fn with_step_limit(mut self, n: usize) -> Self {
+        self.step_limit = Some(n);
+        self
+    }
+
+    /// Extracts a term of the e-class `i` with minimal DAG cost.
+    ///
+    /// Panics if the e-class has no finite term, see [DagExtractor::try_extract].
+    pub fn extract<N: Analysis<L>>(
+        &self,
+        i: &AppliedId,
+        eg: &EGraph<L, N>,
+    ) -> DagExtraction<L, CF::Cost> {
+        self.try_extract(i, eg)
+            .unwrap_or_else(|| panic!("{:?}", ExtractError::Unextractable(i.id)))
+    }
+
+    /// Extracts a term of the e-class `i` with minimal DAG cost, or returns `None` if the e-class has no finite term.
+    pub fn try_extract<N: Analysis<L>>(
+        &self,
+        i: &AppliedId,
+        eg: &EGraph<L, N>,
+    ) -> Option<DagExtraction<L, CF::Cost>> {
+        let root = eg.find_applied_id(i);
+        if !self.candidates.contains_key(&root.id) {
+            return None;
+        }
+
+        let mut search = Search {
+            candidates: &self.candidates,
+            root: root.clone(),
+            steps: 0,
+            step_limit: self.step_limit,
+            best: (HashMap::default(), CF::Cost::default()),
+            abandoned: None,
+        };
+        let (greedy_cost, _) = search.evaluate(&self.greedy)?;
+        search.best = (self.greedy.clone(), greedy_cost);
+        search.run(&mut HashMap::default());
+
+        let (choice, cost) = search.best;
+        let lower_bound = match search.abandoned {
+            Some(lb) if lb < cost => lb,
+            _ => cost.clone(),
+        };
+        Some(DagExtraction {
+            term: extract_choice(&root, &choice)?,
+            optimal: lower_bound == cost,
+            cost,
+            lower_bound,
+        })
+    }
+}
+
+impl<'a, L: Language, C: Ord + Clone + Default + Add<Output = C>> Search<'a, L, C> {
+    fn run(&mut self, choice: &mut HashMap<Id, L>) {
+        let Some((lb, open)) = self.evaluate(choice) else {
+            return;
+        };
+        if lb >= self.best.1 {
+            return;
+        }
+
+        if self.step_limit.is_some_and(|n| self.steps >= n) {
+            if self.abandoned.as_ref().map(|x| lb < *x).unwrap_or(true) {
+                self.abandoned = Some(lb);
+            }
+            return;
+        }
+        self.steps += 1;
+
+        let Some(open) = open else {
+            // every reachable e-class has a choice, so `lb` is the exact cost.
+            self.best = (choice.clone(), lb);
+            return;
+        };
+
+        for (x, _) in self.candidates.get(&open).into_iter().flatten() {
+            choice.insert(open, x.clone());
+            self.run(choice);
+        }
+        choice.remove(&open);
+    }
+
+    // Returns a lower bound for the DAG cost of all completions of `choice`,
+    // and the first reachable e-class without a choice.
+    // Returns `None` if `choice` is cyclic, or reaches an e-class without a finite term.
+    fn evaluate(&self, choice: &HashMap<Id, L>) -> Option<(C, Option<Id>)> {
+        if self.is_cyclic(choice) {
+            return None;
+        }
+
+        let mut cost = C::default();
+        let mut open = None;
+        let mut seen: HashSet<AppliedId> = HashSet::default();
+        let mut stack = vec![self.root.clone()];
+        while let Some(a) = stack.pop() {
+            if !seen.insert(a.clone()) {
+                continue;
+            }
+            let Some(x) = choice.get(&a.id) else {
+                // any completion needs to pay for at least the cheapest e-node of this instance.
+                cost = cost + self.candidates.get(&a.id)?.first()?.1.clone();
+                open = open.or(Some(a.id));
+                continue;
+            };
+            let (_, c) = self.candidates.get(&a.id)?.iter().find(|(y, _)| y == x)?;
+            cost = cost + c.clone();
+            let x = x.apply_slotmap(&a.m);
+            for child in x.applied_id_occurrences().into_iter().rev() {
+                stack.push(child.clone());
+            }
+        }
+        Some((cost, open))
+    }
+
+    // whether the chosen e-nodes reachable from the root form a cycle.
+    fn is_cyclic(&self, choice: &HashMap<Id, L>) -> bool {
+        // `false` means "on the stack", `true` means "done".
+        let mut state: HashMap<Id, bool> = HashMap::default();
+        let mut stack = vec![(self.root.id, false)];
+        while let Some((i, exiting)) = stack.pop() {
+            if exiting {
+                state.insert(i, true);
+                continue;
+            }
+            match state.get(&i) {
+                Some(true) => continue,
+                Some(false) => return true,
+                None => {}
+            }
+            state.insert(i, false);
+            stack.push((i, true));
+            if let Some(x) = choice.get(&i) {
+                for child in x.applied_id_occurrences() {
+                    match state.get(&child.id) {
+                        Some(true) => {}
+                        Some(false) => return true,
+                        None => stack.push((child.id, false)),
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+fn extract_choice<L: Language>(i: &AppliedId, choice: &HashMap<Id, L>) -> Option<RecExpr<L>> {
+    let l = choice.get(&i.id)?.apply_slotmap(&i.m);
+    let children = l
+        .applied_id_occurrences()
+        .into_iter()
+        .map(|child| extract_choice(child, choice))
+        .collect::<Option<_>>()?;
+    Some(RecExpr { node: l, children })
+}
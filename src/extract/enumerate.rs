@@ -0,0 +1,262 @@
+use crate::*;
+
+/// Counts and enumerates the terms represented by the e-classes of an [EGraph].
+///
+/// The size of a term is its number of nodes, as in [AstSize].
+/// Terms are considered up to alpha-equivalence, i.e. terms that only differ in the names of their bound or redundant slots are only counted once.
+/// The symmetries of an e-class are taken into account:
+/// if an e-class `c(x, y)` contains `(add x y)` and is symmetric in `x` and `y`, then it also represents `(add y x)`.
+///
+/// Counts saturate at `u64::MAX`.
+pub struct TermEnumerator<'a, L: Language, N: Analysis<L>> {
+    eg: &'a EGraph<L, N>,
+
+    // the e-nodes of each e-class, written in terms of the slots of the e-class.
+    // This includes the images of the e-nodes under the symmetry group of the e-class,
+    // but no two of them represent a common term.
+    nodes: HashMap<Id, Vec<L>>,
+
+    // `counts[i][k]` is the number of terms of size `k` in the e-class `i`.
+    counts: HashMap<Id, Vec<u64>>,
+
+    // the terms of each e-class by size, written in terms of the slots of the e-class.
+    terms: HashMap<(Id, usize), Vec<RecExpr<L>>>,
+}
+
+impl<'a, L: Language, N: Analysis<L>> TermEnumerator<'a, L, N> {
+    pub fn new(eg: &'a EGraph<L, N>) -> Self {
+        if CHECKS {
+            eg.check();
+        }
+
+        let mut nodes = HashMap::default();
+        for i in eg.ids() {
+            let slots = eg.slots(i);
+            let perms: Vec<Perm> = eg.classes[&i]
+                .group
+                .all_perms()
+                .into_iter()
+                .map(|p| p.elem)
+                .collect();
+
+            // alpha-invariant keys of all e-nodes that represent the same terms as some e-node in `out`.
+            let mut seen: HashSet<(L, Vec<(Slot, Slot)>)> = HashSet::default();
+            let mut out = Vec::new();
+            for x in eg.sorted_enodes(i) {
+                let x = eg.class_nf(&x);
+                for p in &perms {
+                    let y = x.apply_slotmap_fresh(p);
+                    let keys: Vec<_> = eg
+                        .get_group_compatible_variants(&y)
+                        .iter()
+                        .map(|v| alpha_key(v, &slots))
+                        .collect();
+                    if keys.iter().any(|k| seen.contains(k)) {
+                        continue;
+                    }
+                    seen.extend(keys);
+                    out.push(y);
+                }
+            }
+            nodes.insert(i, out);
+        }
+
+        let counts = eg.ids().into_iter().map(|i| (i, vec![0])).collect();
+
+        Self {
+            eg,
+            nodes,
+            counts,
+            terms: HashMap::default(),
+        }
+    }
+
+    /// Returns the number of terms of size at most `n` in the e-class `i`.
+    pub fn count(&mut self, i: Id, n: usize) -> u64 {
+        self.count_by_size(i, n)
+            .into_iter()
+            .fold(0, |acc, x| acc.saturating_add(x))
+    }
+
+    /// Returns a vector `v` of length `n+1`, where `v[k]` is the number of terms of size `k` in the e-class `i`.
+    pub fn count_by_size(&mut self, i: Id, n: usize) -> Vec<u64> {
+        self.compute_counts(n);
+        self.counts[&self.eg.find_id(i)][..=n].to_vec()
+    }
+
+    /// Returns all terms of size `k` in the e-class `i`.
+    pub fn terms_of_size(&mut self, i: &AppliedId, k: usize) -> Vec<RecExpr<L>> {
+        let i = self.eg.find_applied_id(i);
+        self.compute_terms(i.id, k);
+        self.terms[&(i.id, k)]
+            .iter()
+            .map(|t| rename_rec_expr(t, &i.m))
+            .collect()
+    }
+
+    /// Enumerates all terms of size at most `n` in the e-class `i`, ordered by size.
+    pub fn into_terms(self, i: &AppliedId, n: usize) -> Terms<'a, L, N> {
+        Terms {
+            root: self.eg.find_applied_id(i),
+            enumerator: self,
+            max_size: n,
+            size: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    // computes the counts of all e-classes up to size `n`, level by level.
+    fn compute_counts(&mut self, n: usize) {
+        let computed = self.counts.values().next().map(|v| v.len()).unwrap_or(0);
+        for k in computed..=n {
+            let mut level = Vec::new();
+            for (i, xs) in &self.nodes {
+                let mut c = 0u64;
+                for x in xs {
+                    c = c.saturating_add(self.count_node(x, k));
+                }
+                level.push((*i, c));
+            }
+            for (i, c) in level {
+                self.counts.get_mut(&i).unwrap().push(c);
+            }
+        }
+    }
+
+    // the number of terms of size `k` with `x` at the top.
+    // Requires the counts of all sizes below `k`.
+    fn count_node(&self, x: &L, k: usize) -> u64 {
+        if k == 0 {
+            return 0;
+        }
+
+        // `ways[s]` is the number of ways to pick terms of total size `s` for the children processed so far.
+        let mut ways = vec![0u64; k];
+        ways[0] = 1;
+        for child in x.applied_id_occurrences() {
+            let child_counts = &self.counts[&child.id];
+            let mut next = vec![0u64; k];
+            for (s, w) in ways.iter().enumerate() {
+                if *w == 0 {
+                    continue;
+                }
+                for (cs, cc) in child_counts.iter().enumerate().take(k - s).skip(1) {
+                    next[s + cs] = next[s + cs].saturating_add(w.saturating_mul(*cc));
+                }
+            }
+            ways = next;
+        }
+        ways[k - 1]
+    }
+
+    fn compute_terms(&mut self, i: Id, k: usize) {
+        if self.terms.contains_key(&(i, k)) {
+            return;
+        }
+        self.compute_counts(k);
+
+        let slots = self.eg.slots(i);
+        let mut out = Vec::new();
+        for x in self.nodes[&i].clone() {
+            let children: Vec<Id> = x.ids();
+            if k == 0 || self.count_node(&x, k) == 0 {
+                continue;
+            }
+
+            for sizes in compositions(k - 1, children.len()) {
+                if children
+                    .iter()
+                    .zip(&sizes)
+                    .any(|(c, s)| self.counts[c][*s] == 0)
+                {
+                    continue;
+                }
+                for (c, s) in children.iter().zip(&sizes) {
+                    self.compute_terms(*c, *s);
+                }
+
+                let child_terms: Vec<Vec<RecExpr<L>>> = children
+                    .iter()
+                    .zip(&sizes)
+                    .map(|(c, s)| self.terms[&(*c, *s)].clone())
+                    .collect();
+                for combination in cartesian(&child_terms) {
+                    // fresh bound slots for every term, so that no slot is captured by a binder of the same name.
+                    let node = x.refresh_internals(slots.clone());
+                    let children = node
+                        .applied_id_occurrences()
+                        .into_iter()
+                        .zip(combination)
+                        .map(|(a, t)| rename_rec_expr(t, &a.m))
+                        .collect();
+                    out.push(RecExpr { node, children });
+                }
+            }
+        }
+        self.terms.insert((i, k), out);
+    }
+}
+
+/// An iterator over the terms of an e-class, ordered by size. See [TermEnumerator::into_terms].
+pub struct Terms<'a, L: Language, N: Analysis<L>> {
+    enumerator: TermEnumerator<'a, L, N>,
+    root: AppliedId,
+    max_size: usize,
+
+    // the size of the terms in `buffer`.
+    size: usize,
+
+    // the remaining terms of the current size, in reverse order.
+    buffer: Vec<RecExpr<L>>,
+}
+
+impl<'a, L: Language, N: Analysis<L>> Iterator for Terms<'a, L, N> {
+    type Item = RecExpr<L>;
+
+    fn next(&mut self) -> Option<RecExpr<L>> {
+        while self.buffer.is_empty() {
+            if self.size >= self.max_size {
+                return None;
+            }
+            self.size += 1;
+            self.buffer = self.enumerator.terms_of_size(&self.root, self.size);
+            self.buffer.reverse();
+        }
+        self.buffer.pop()
+    }
+}
+
+impl<L: Language, N: Analysis<L>> EGraph<L, N> {
+    /// Returns the number of terms of size at most `n` represented by the e-class `i`, see [TermEnumerator].
+    pub fn count_terms(&self, i: Id, n: usize) -> u64 {
+        TermEnumerator::new(self).count(i, n)
+    }
+
+    /// Enumerates the terms of size at most `n` represented by the e-class `i`, ordered by size. See [TermEnumerator].
+    pub fn enumerate_terms(&self, i: &AppliedId, n: usize) -> Terms<'_, L, N> {
+        TermEnumerator::new(self).into_terms(i, n)
+    }
+}
+
+// Two e-nodes have the same key iff they only differ in the names of slots outside of `public`.
+fn alpha_key<L: Language>(x: &L, public: &SmallHashSet<Slot>) -> (L, Vec<(Slot, Slot)>) {
+    let (sh, bij) = x.weak_shape();
+    let mut m: Vec<(Slot, Slot)> = bij.iter().filter(|(_, y)| public.contains(y)).collect();
+    m.sort();
+    (sh, m)
+}
+
+// all ways to write `n` as an ordered sum of `parts` positive numbers.
+fn compositions(n: usize, parts: usize) -> Vec<Vec<usize>> {
+    if parts == 0 {
+        return if n == 0 { vec![Vec::new()] } else { Vec::new() };
+    }
+    let mut out = Vec::new();
+    for first in 1..=n.saturating_sub(parts - 1) {
+        for mut rest in compositions(n - first, parts - 1) {
+            rest.insert(0, first);
+            out.push(rest);
+        }
+    }
+    out
+}
@@ -6,23 +6,94 @@ pub use cost::*;
 mod with_ord;
 pub use with_ord::*;
 
+mod dag;
+pub use dag::*;
+
+mod top_k;
+pub use top_k::*;
+
+mod sample;
+pub use sample::*;
+
+mod enumerate;
+pub use enumerate::*;
+
+mod renaming;
+use renaming::{ClassData, State};
+
 use std::collections::BinaryHeap;
 
+type NodeFilter<L> = Box<dyn Fn(&L) -> bool>;
+
 /// An object used for quickly extracting terms (i.e. [RecExpr]s) using a given [CostFunction].
 ///
 /// Creating an Extractor will setup an extraction-table which then allows you to extract terms from many e-classes efficiently.
 /// It is most useful when doing "bulk" extractions for many classes.
-pub struct Extractor<L: Language, CF: CostFunction<L>> {
+///
+/// The cost function may be any [SlotCostFunction], and hence in particular any [CostFunction].
+/// If it is not [SlotCostFunction::RENAMING_INVARIANT], the best term of an e-class depends on how its slots are renamed.
+/// Then the extraction-table covers the renamings that occur in the e-graph, and other renamings are computed when they are extracted.
+///
+/// After the e-graph changed, [Extractor::refresh] updates the extraction-table without rebuilding it from scratch.
+pub struct Extractor<L: Language, CF: SlotCostFunction<L>> {
     pub(crate) map: HashMap<Id, WithOrdRev<L, CF::Cost>>,
+    cost_fn: CF,
+
+    // only e-nodes accepted by this filter are extracted.
+    filter: Option<NodeFilter<L>>,
+
+    // the `EGraph::change_count` at the last refresh.
+    refreshed_at: usize,
+
+    // Only used if the cost function is not `RENAMING_INVARIANT`:
+    // the best term of each solved renaming of an e-class, see `renaming.rs`,
+    states: HashMap<State, WithOrdRev<L, CF::Cost>>,
+
+    // and the e-nodes and symmetries of each e-class that these are computed from.
+    classes: HashMap<Id, ClassData<L>>,
 }
 
-impl<L: Language, CF: CostFunction<L>> Extractor<L, CF> {
+impl<L: Language, CF: SlotCostFunction<L>> Extractor<L, CF> {
     #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
     pub fn new<N: Analysis<L>>(eg: &EGraph<L, N>, cost_fn: CF) -> Self {
+        Self::with_filter_impl(eg, cost_fn, None)
+    }
+
+    /// Creates an extractor that only uses e-nodes for which `filter` returns true, e.g. to avoid `let` nodes.
+    ///
+    /// E-classes that have no term consisting only of such e-nodes are unextractable, see [Extractor::try_extract].
+    pub fn new_filtered<N: Analysis<L>>(
+        eg: &EGraph<L, N>,
+        cost_fn: CF,
+        filter: impl Fn(&L) -> bool + 'static,
+    ) -> Self {
+        Self::with_filter_impl(eg, cost_fn, Some(Box::new(filter)))
+    }
+
+    fn with_filter_impl<N: Analysis<L>>(
+        eg: &EGraph<L, N>,
+        cost_fn: CF,
+        filter: Option<NodeFilter<L>>,
+    ) -> Self {
         if CHECKS {
             eg.check();
         }
 
+        if !CF::RENAMING_INVARIANT {
+            let mut out = Self {
+                map: HashMap::default(),
+                cost_fn,
+                filter,
+                refreshed_at: 0,
+                states: HashMap::default(),
+                classes: HashMap::default(),
+            };
+            out.refresh_states(eg);
+            return out;
+        }
+
+        let allowed = |x: &L| filter.as_ref().map(|f| f(x)).unwrap_or(true);
+
         // all the L in `map` and `queue` have to be
         // - in "normal-form", i.e. calling lookup on them yields an identity AppliedId.
         // - every internal slot needs to be refreshed.
@@ -33,9 +104,9 @@ impl<L: Language, CF: CostFunction<L>> Extractor<L, CF> {
 
         for id in eg.ids() {
             for x in eg.enodes(id) {
-                if x.applied_id_occurrences().is_empty() {
+                if x.applied_id_occurrences().is_empty() && allowed(&x) {
                     let x = eg.class_nf(&x);
-                    let c = cost_fn.cost(&x, |_| panic!());
+                    let c = cost_fn.slot_cost(&x, &eg.slots(id), |_| panic!());
                     queue.push(WithOrdRev(x, c));
                 }
             }
@@ -52,6 +123,7 @@ impl<L: Language, CF: CostFunction<L>> Extractor<L, CF> {
                 if x.applied_id_occurrences()
                     .iter()
                     .all(|i| map.contains_key(&i.id))
+                    && allowed(&x)
                 {
                     if eg
                         .lookup(&x)
@@ -61,36 +133,288 @@ impl<L: Language, CF: CostFunction<L>> Extractor<L, CF> {
                         continue;
                     }
                     let x = eg.class_nf(&x);
-                    let c = cost_fn.cost(&x, |i| map[&i].1.clone());
+                    let slots = eg.slots(eg.lookup(&x).unwrap().id);
+                    let c = cost_fn.slot_cost(&x, &slots, |i| map[&i.id].1.clone());
                     queue.push(WithOrdRev(x, c));
                 }
             }
         }
 
-        Self { map }
+        Self {
+            map,
+            cost_fn,
+            filter,
+            refreshed_at: eg.change_count,
+            states: HashMap::default(),
+            classes: HashMap::default(),
+        }
+    }
+
+    /// Updates the extraction-table after `eg` changed, e.g. by further rewriting.
+    ///
+    /// `eg` has to be the e-graph that this extractor was created for.
+    /// Only e-classes whose e-nodes or slots changed since the last refresh are recomputed,
+    /// and improved costs are propagated to their users.
+    pub fn refresh<N: Analysis<L>>(&mut self, eg: &EGraph<L, N>) {
+        if CHECKS {
+            eg.check();
+        }
+
+        if !CF::RENAMING_INVARIANT {
+            self.refresh_states(eg);
+            return;
+        }
+
+        // As e-graphs only grow, the costs in `map` stay valid for the merged e-classes.
+        // But the e-nodes of changed e-classes might be outdated, so they are "stale" until recomputed.
+        self.map.retain(|i, _| eg.is_alive(*i));
+        let changed: Vec<Id> = eg
+            .ids()
+            .into_iter()
+            .filter(|i| eg.classes[i].changed_at > self.refreshed_at)
+            .collect();
+        let mut stale: HashSet<Id> = changed
+            .iter()
+            .copied()
+            .filter(|i| self.map.contains_key(i))
+            .collect();
+
+        let mut queue: BinaryHeap<WithOrdRev<L, CF::Cost>> = BinaryHeap::new();
+        for &id in &changed {
+            for x in eg.enodes(id) {
+                self.push_candidate(&x, eg, &mut queue);
+            }
+        }
+
+        while let Some(WithOrdRev(enode, c)) = queue.pop() {
+            let i = eg.lookup(&enode).unwrap().id;
+            let improved = match self.map.get(&i) {
+                None => true,
+                Some(old) if c < old.1 => true,
+                Some(old) if c == old.1 && stale.contains(&i) => false,
+                Some(_) => continue,
+            };
+            self.map.insert(i, WithOrdRev(enode, c));
+            stale.remove(&i);
+
+            if improved {
+                for x in eg.usages(i) {
+                    self.push_candidate(&x, eg, &mut queue);
+                }
+            }
+        }
+
+        // only happens for non-monotonic cost functions.
+        for i in stale {
+            self.map.remove(&i);
+        }
+
+        self.refreshed_at = eg.change_count;
+    }
+
+    // pushes `x` onto the queue, if all its children have a cost.
+    fn push_candidate<N: Analysis<L>>(
+        &self,
+        x: &L,
+        eg: &EGraph<L, N>,
+        queue: &mut BinaryHeap<WithOrdRev<L, CF::Cost>>,
+    ) {
+        let ids = x.ids();
+        if !ids.iter().all(|i| self.map.contains_key(&eg.find_id(*i))) {
+            return;
+        }
+        if self.filter.as_ref().is_some_and(|f| !f(x)) {
+            return;
+        }
+        let x = eg.class_nf(x);
+        let slots = eg.slots(eg.lookup(&x).unwrap().id);
+        let c = self
+            .cost_fn
+            .slot_cost(&x, &slots, |i| self.map[&eg.find_id(i.id)].1.clone());
+        queue.push(WithOrdRev(x, c));
     }
 
+    /// Extracts the best term of the e-class `i`.
+    ///
+    /// Panics if the e-class has no finite term, see [Extractor::try_extract].
     #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
     pub fn extract<N: Analysis<L>>(&self, i: &AppliedId, eg: &EGraph<L, N>) -> RecExpr<L> {
+        self.try_extract(i, eg).unwrap_or_else(|e| panic!("{e:?}"))
+    }
+
+    /// Extracts the best term of the e-class `i`, or fails if the extractor found no finite term for it.
+    ///
+    /// This happens for e-classes that were added to the e-graph after the extractor was created,
+    /// or for e-classes whose e-nodes all depend on cycles.
+    pub fn try_extract<N: Analysis<L>>(
+        &self,
+        i: &AppliedId,
+        eg: &EGraph<L, N>,
+    ) -> Result<RecExpr<L>, ExtractError> {
+        if !CF::RENAMING_INVARIANT {
+            return self.build_state(&self.state(&eg.find_applied_id(i)));
+        }
+
         let i = eg.find_applied_id(i);
 
+        let Some(WithOrdRev(l, _)) = self.map.get(&i.id) else {
+            return Err(ExtractError::Unextractable(i.id));
+        };
+
         let mut children = Vec::new();
 
         // do I need to refresh some slots here?
-        let l = self.map[&i.id].0.apply_slotmap(&i.m);
+        let l = l.apply_slotmap(&i.m);
         for child in l.applied_id_occurrences() {
-            let n = self.extract(&child, eg);
+            let n = self.try_extract(child, eg)?;
             children.push(n);
         }
 
-        RecExpr { node: l, children }
+        Ok(RecExpr { node: l, children })
+    }
+
+    /// Extracts the best term of the e-class `i` in which none of the `forbidden` slots occur free.
+    ///
+    /// All terms of an e-class mention its slots, but redundant slots can be renamed arbitrarily.
+    /// Hence this fails with [ExtractError::ForbiddenSlot] iff a forbidden slot is passed to `i` as a non-redundant slot.
+    /// Any other slot, which is not bound within the term, is renamed to a fresh slot.
+    pub fn try_extract_avoiding<N: Analysis<L>>(
+        &self,
+        i: &AppliedId,
+        forbidden: &SmallHashSet<Slot>,
+        eg: &EGraph<L, N>,
+    ) -> Result<RecExpr<L>, ExtractError> {
+        let i = eg.find_applied_id(i);
+        if let Some(s) = i.m.values().into_iter().find(|s| forbidden.contains(s)) {
+            return Err(ExtractError::ForbiddenSlot(s));
+        }
+        self.extract_fresh(&i, eg)
+    }
+
+    // like `try_extract`, but renames redundant slots to fresh ones.
+    fn extract_fresh<N: Analysis<L>>(
+        &self,
+        i: &AppliedId,
+        eg: &EGraph<L, N>,
+    ) -> Result<RecExpr<L>, ExtractError> {
+        // there, redundant slots are already fresh.
+        if !CF::RENAMING_INVARIANT {
+            return self.try_extract(i, eg);
+        }
+
+        let i = eg.find_applied_id(i);
+
+        let Some(WithOrdRev(l, _)) = self.map.get(&i.id) else {
+            return Err(ExtractError::Unextractable(i.id));
+        };
+
+        let mut m = i.m.clone();
+        for s in l.slots() {
+            if !m.contains_key(s) {
+                m.insert(s, Slot::fresh());
+            }
+        }
+        let l = l.apply_slotmap(&m);
+
+        let mut children = Vec::new();
+        for child in l.applied_id_occurrences() {
+            children.push(self.extract_fresh(child, eg)?);
+        }
+
+        Ok(RecExpr { node: l, children })
+    }
+
+    /// Extracts the best term of the e-class `i`, together with a proof that it's equal to the syntactic term of `i`.
+    ///
+    /// The syntactic term of `i` is [EGraph::get_syn_expr], i.e. the term that was added if `i` was returned by [EGraph::add_syn_expr].
+    /// The proof is assembled from the proofs already stored in the e-graph, so the original term isn't added again.
+    /// Only the extracted term is added syntactically, as proofs can only talk about terms that are in the e-graph.
+    /// This doesn't change which terms are equal, but the extractor might need a [Extractor::refresh] before it's used again.
+    ///
+    /// Panics if the e-class has no finite term, or if explanations are disabled, see [EGraph::with_explanations].
+    #[cfg(feature = "explanations")]
+    pub fn extract_proven<N: Analysis<L>>(
+        &self,
+        i: &AppliedId,
+        eg: &mut EGraph<L, N>,
+    ) -> ProvenExtraction<L> {
+        let root = eg.synify_app_id(i.clone());
+        let term = self
+            .extract_fresh(&root, eg)
+            .unwrap_or_else(|e| panic!("{e:?}"));
+        let j = eg.add_syn_expr(term.clone());
+        let proof = eg.prove_equivalence(&root, &j).unwrap();
+        ProvenExtraction { term, proof }
     }
 
     pub fn get_best_cost<N: Analysis<L>>(&self, i: &AppliedId) -> CF::Cost {
-        self.map[&i.id].1.clone()
+        let c = if CF::RENAMING_INVARIANT {
+            self.map.get(&i.id).map(|x| x.1.clone())
+        } else {
+            self.state_cost(&self.state(i))
+        };
+        c.unwrap_or_else(|| panic!("{:?}", ExtractError::Unextractable(i.id)))
+    }
+
+    /// Returns the cost of the best term of the e-class `i`, or `None` if there is no finite term.
+    pub fn try_best_cost<N: Analysis<L>>(
+        &self,
+        i: &AppliedId,
+        eg: &EGraph<L, N>,
+    ) -> Option<CF::Cost> {
+        if !CF::RENAMING_INVARIANT {
+            return self.state_cost(&self.state(&eg.find_applied_id(i)));
+        }
+
+        let i = eg.find_id(i.id);
+        self.map.get(&i).map(|x| x.1.clone())
+    }
+
+    /// Returns the e-classes among `roots` that have no finite term, in ascending order and without duplicates.
+    pub fn unextractable<N: Analysis<L>>(&self, roots: &[AppliedId], eg: &EGraph<L, N>) -> Vec<Id> {
+        let mut out: Vec<Id> = roots
+            .iter()
+            .map(|i| eg.find_id(i.id))
+            .filter(|i| !self.map.contains_key(i))
+            .collect();
+        out.sort();
+        out.dedup();
+        out
     }
 }
 
+/// The error returned by [Extractor::try_extract].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractError {
+    /// The extractor found no finite term for this e-class.
+    Unextractable(Id),
+
+    /// This slot occurs free in every term of the e-class.
+    ForbiddenSlot(Slot),
+}
+
+/// The result of [Extractor::extract_proven].
+#[cfg(feature = "explanations")]
+#[derive(Debug, Clone)]
+pub struct ProvenExtraction<L: Language> {
+    pub term: RecExpr<L>,
+
+    /// Proves that the syntactic term of the e-class is equal to `term`.
+    /// Use [ProvenEqRaw::to_flat_string] for a step-by-step explanation.
+    pub proof: ProvenEq,
+}
+
+/// Extracts the smallest term of `i`, together with a proof that it's equal to the syntactic term of `i`.
+///
+/// See [Extractor::extract_proven].
+#[cfg(feature = "explanations")]
+pub fn ast_size_extract_proven<L: Language, N: Analysis<L>>(
+    i: &AppliedId,
+    eg: &mut EGraph<L, N>,
+) -> ProvenExtraction<L> {
+    Extractor::new(eg, AstSize).extract_proven(i, eg)
+}
+
 pub fn ast_size_extract<L: Language, N: Analysis<L>>(
     i: &AppliedId,
     eg: &EGraph<L, N>,
@@ -105,7 +429,7 @@ pub fn extract<L: Language, N: Analysis<L>, CF: CostFunction<L> + Default>(
 ) -> RecExpr<L> {
     let cost_fn = CF::default();
     let extractor = Extractor::<L, CF>::new(eg, cost_fn);
-    let out = extractor.extract(&i, eg);
+    let out = extractor.extract(i, eg);
     if CHECKS {
         let i = eg.find_id(i.id);
         let cost_fn = CF::default();
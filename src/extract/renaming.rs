@@ -0,0 +1,252 @@
+use crate::*;
+
+use std::collections::BinaryHeap;
+
+// An e-class together with a renaming of its slots.
+// The renaming is the smallest one that is equivalent up to the symmetries of the e-class, as those give the same terms.
+pub(crate) type State = (Id, SlotMap);
+
+// The parts of an e-class that its best terms are computed from.
+pub(crate) struct ClassData<L> {
+    // the e-nodes accepted by the filter, in class normal form.
+    // Their bound and redundant slots are fresh, and keep their names in all states.
+    nodes: Vec<L>,
+
+    // the symmetries of the e-class, or nothing if it has none.
+    perms: Vec<Perm>,
+}
+
+// An e-node that is a candidate for the best term of `state`.
+// Its cost can be computed once all of its `children` are solved.
+struct Candidate<L> {
+    state: State,
+    node: L,
+    children: Vec<State>,
+    missing: usize,
+}
+
+impl<L: Language, CF: SlotCostFunction<L>> Extractor<L, CF> {
+    // The state of `i`, which has to be canonical.
+    pub(crate) fn state(&self, i: &AppliedId) -> State {
+        let Some(m) = self
+            .classes
+            .get(&i.id)
+            .and_then(|c| c.perms.iter().map(|p| p.compose(&i.m)).min())
+        else {
+            return (i.id, i.m.clone());
+        };
+        (i.id, m)
+    }
+
+    // Computes the best term of each state reachable from `roots` that isn't in `self.states` yet,
+    // written in terms of the slots of the state.
+    pub(crate) fn solve(
+        &self,
+        roots: impl IntoIterator<Item = State>,
+    ) -> HashMap<State, WithOrdRev<L, CF::Cost>> {
+        let mut alts: HashMap<Slot, Vec<Slot>> = HashMap::default();
+
+        let mut candidates: Vec<Candidate<L>> = Vec::new();
+        // for each state, the candidates that have it as a child.
+        let mut usages: HashMap<State, Vec<usize>> = HashMap::default();
+        let mut seen: HashSet<State> = HashSet::default();
+        let mut todo: Vec<State> = roots.into_iter().collect();
+
+        while let Some(state) = todo.pop() {
+            if self.states.contains_key(&state) || !seen.insert(state.clone()) {
+                continue;
+            }
+            let Some(c) = self.classes.get(&state.0) else {
+                continue;
+            };
+            for n in &c.nodes {
+                let node = instantiate(n, &state.1, &mut alts);
+                let mut children = Vec::new();
+                for child in node.applied_id_occurrences() {
+                    let s = self.state(child);
+                    if !self.states.contains_key(&s) && !children.contains(&s) {
+                        children.push(s);
+                    }
+                }
+                for s in &children {
+                    usages.entry(s.clone()).or_default().push(candidates.len());
+                    todo.push(s.clone());
+                }
+                candidates.push(Candidate {
+                    state: state.clone(),
+                    node,
+                    missing: children.len(),
+                    children,
+                });
+            }
+        }
+
+        let mut table: HashMap<State, WithOrdRev<L, CF::Cost>> = HashMap::default();
+        let cost = |x: &Candidate<L>, table: &HashMap<State, WithOrdRev<L, CF::Cost>>| {
+            if CHECKS {
+                assert!(x.children.iter().all(|s| table.contains_key(s)));
+            }
+            self.cost_fn.slot_cost(&x.node, &x.state.1.values(), |i| {
+                let s = self.state(i);
+                table.get(&s).unwrap_or_else(|| &self.states[&s]).1.clone()
+            })
+        };
+
+        let mut queue: BinaryHeap<WithOrdRev<usize, CF::Cost>> = BinaryHeap::new();
+        for (k, x) in candidates.iter().enumerate() {
+            if x.missing == 0 {
+                queue.push(WithOrdRev(k, cost(x, &table)));
+            }
+        }
+
+        while let Some(WithOrdRev(k, c)) = queue.pop() {
+            let state = candidates[k].state.clone();
+            if table.contains_key(&state) {
+                continue;
+            }
+            table.insert(state.clone(), WithOrdRev(candidates[k].node.clone(), c));
+
+            for &p in usages.get(&state).into_iter().flatten() {
+                candidates[p].missing -= 1;
+                if candidates[p].missing == 0 {
+                    queue.push(WithOrdRev(p, cost(&candidates[p], &table)));
+                }
+            }
+        }
+
+        table
+    }
+
+    // The best term of `state` and its cost, looked up in `extra` or `self.states`.
+    fn solved<'a>(
+        &'a self,
+        extra: &'a HashMap<State, WithOrdRev<L, CF::Cost>>,
+        state: &State,
+    ) -> Option<&'a WithOrdRev<L, CF::Cost>> {
+        extra.get(state).or_else(|| self.states.get(state))
+    }
+
+    // The cost of the best term of `state`, solving it if it's not in `self.states` yet.
+    pub(crate) fn state_cost(&self, state: &State) -> Option<CF::Cost> {
+        match self.states.get(state) {
+            Some(x) => Some(x.1.clone()),
+            None => self.solve([state.clone()]).remove(state).map(|x| x.1),
+        }
+    }
+
+    // Builds the best term of `state`, solving it if it's not in `self.states` yet.
+    pub(crate) fn build_state(&self, state: &State) -> Result<RecExpr<L>, ExtractError> {
+        let extra = if self.states.contains_key(state) {
+            HashMap::default()
+        } else {
+            self.solve([state.clone()])
+        };
+        self.build(&extra, state)
+    }
+
+    fn build(
+        &self,
+        extra: &HashMap<State, WithOrdRev<L, CF::Cost>>,
+        state: &State,
+    ) -> Result<RecExpr<L>, ExtractError> {
+        let Some(WithOrdRev(l, _)) = self.solved(extra, state) else {
+            return Err(ExtractError::Unextractable(state.0));
+        };
+
+        let mut children = Vec::new();
+        for child in l.applied_id_occurrences() {
+            children.push(self.build(extra, &self.state(child))?);
+        }
+
+        Ok(RecExpr {
+            node: l.clone(),
+            children,
+        })
+    }
+
+    // Updates `self.states` after `eg` changed, and the best term of each e-class with its own slots in `self.map`.
+    // Only the e-classes that changed since the last refresh and the e-classes above them are solved again,
+    // as the best terms of all other e-classes only depend on unchanged e-classes.
+    pub(crate) fn refresh_states<N: Analysis<L>>(&mut self, eg: &EGraph<L, N>) {
+        let mut dirty: HashSet<Id> = eg
+            .ids()
+            .into_iter()
+            .filter(|i| eg.classes[i].changed_at > self.refreshed_at)
+            .collect();
+        let mut stack: Vec<Id> = dirty.iter().copied().collect();
+        while let Some(i) = stack.pop() {
+            for x in eg.usages(i) {
+                let j = eg.lookup(&x).unwrap().id;
+                if dirty.insert(j) {
+                    stack.push(j);
+                }
+            }
+        }
+
+        self.classes
+            .retain(|i, _| eg.is_alive(*i) && !dirty.contains(i));
+        let classes = &self.classes;
+        self.states.retain(|(i, _), _| classes.contains_key(i));
+        self.map.retain(|i, _| classes.contains_key(i));
+
+        for &i in &dirty {
+            let nodes = eg
+                .enodes(i)
+                .into_iter()
+                .filter(|x| self.filter.as_ref().map(|f| f(x)).unwrap_or(true))
+                .map(|x| eg.class_nf(&x))
+                .collect();
+            let grp = &eg.classes[&i].group;
+            let perms = if grp.count() == 1 {
+                Vec::new()
+            } else {
+                grp.all_perms().into_iter().map(|p| p.elem).collect()
+            };
+            self.classes.insert(i, ClassData { nodes, perms });
+        }
+
+        let roots: Vec<(Id, State)> = dirty
+            .into_iter()
+            .map(|i| (i, self.state(&eg.mk_sem_identity_applied_id(i))))
+            .collect();
+        let table = self.solve(roots.iter().map(|(_, s)| s.clone()));
+        for (i, s) in roots {
+            if let Some(WithOrdRev(l, c)) = table.get(&s) {
+                self.map.insert(i, WithOrdRev(l.clone(), c.clone()));
+            }
+        }
+        self.states.extend(table);
+        self.refreshed_at = eg.change_count;
+    }
+}
+
+// Renames the slots of the e-class of `n` by `m`.
+// The bound and redundant slots of `n` keep their names, unless they collide with the renamed slots.
+// Then they are replaced by alternative names from `alts`, which are re-used for later collisions.
+fn instantiate<L: Language>(n: &L, m: &SlotMap, alts: &mut HashMap<Slot, Vec<Slot>>) -> L {
+    let mut r = m.clone();
+    let mut taken = m.values();
+    for s in n.all_slot_occurrences() {
+        if r.contains_key(s) {
+            continue;
+        }
+        let mut t = s;
+        let mut k = 0;
+        while taken.contains(&t) {
+            let v = alts.entry(s).or_default();
+            if v.len() == k {
+                v.push(Slot::fresh());
+            }
+            t = v[k];
+            k += 1;
+        }
+        r.insert(s, t);
+        taken.insert(t);
+    }
+
+    let mut out = n.clone();
+    for x in out.all_slot_occurrences_mut() {
+        *x = r[*x];
+    }
+    out
+}
@@ -0,0 +1,161 @@
+use crate::*;
+
+/// Draws random terms from the e-classes of an [EGraph], e.g. to fuzz rewrite rules.
+///
+/// Every sampled term is built top-down from e-nodes of the e-class, so it is represented by that e-class.
+/// Bound slots and redundant slots get fresh names, so that they never clash with the slots passed to the e-class.
+///
+/// The sampler is deterministic for a given seed and e-graph.
+pub struct Sampler<'a, L: Language, N: Analysis<L>> {
+    eg: &'a EGraph<L, N>,
+    rng: SplitMix64,
+    max_depth: usize,
+    weight: Box<dyn Fn(&L) -> f64 + 'a>,
+
+    // the e-nodes of each e-class with the height of their smallest term, unless it's infinite.
+    nodes: HashMap<Id, Vec<(L, Option<usize>)>>,
+}
+
+impl<'a, L: Language, N: Analysis<L>> Sampler<'a, L, N> {
+    /// Creates a sampler that picks e-nodes uniformly, and samples terms of depth at most 10.
+    pub fn new(eg: &'a EGraph<L, N>, seed: u64) -> Self {
+        let mut nodes: HashMap<Id, Vec<(L, Option<usize>)>> = eg
+            .ids()
+            .into_iter()
+            .map(|i| {
+                (
+                    i,
+                    eg.sorted_enodes(i).into_iter().map(|x| (x, None)).collect(),
+                )
+            })
+            .collect();
+
+        // the height of the smallest term of each e-class, computed by fixpoint iteration.
+        let mut height: HashMap<Id, usize> = HashMap::default();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in eg.ids() {
+                for (x, h) in nodes.get_mut(&i).unwrap() {
+                    let mut x_height = 1;
+                    for c in x.applied_id_occurrences() {
+                        match height.get(&c.id) {
+                            Some(ch) => x_height = x_height.max(ch + 1),
+                            None => {
+                                x_height = usize::MAX;
+                                break;
+                            }
+                        }
+                    }
+                    if x_height == usize::MAX {
+                        continue;
+                    }
+                    *h = Some(x_height);
+                    if height.get(&i).map(|old| x_height < *old).unwrap_or(true) {
+                        height.insert(i, x_height);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        Sampler {
+            eg,
+            rng: SplitMix64(seed),
+            max_depth: 10,
+            weight: Box::new(|_| 1.0),
+            nodes,
+        }
+    }
+
+    /// Sets the maximal depth of the sampled terms, where a leaf has depth 1.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Picks each e-node with a probability proportional to its (non-negative) weight.
+    ///
+    /// The weight function gets the e-node as it's stored in its e-class.
+    /// If all possible e-nodes have weight zero, one of them is picked uniformly.
+    pub fn with_weights(mut self, weight: impl Fn(&L) -> f64 + 'a) -> Self {
+        self.weight = Box::new(weight);
+        self
+    }
+
+    /// Samples a term of the e-class `i`.
+    ///
+    /// Returns `None` if the e-class has no term within the depth bound.
+    pub fn sample(&mut self, i: &AppliedId) -> Option<RecExpr<L>> {
+        let i = self.eg.find_applied_id(i);
+        self.sample_rec(&i, self.max_depth)
+    }
+
+    fn sample_rec(&mut self, i: &AppliedId, depth: usize) -> Option<RecExpr<L>> {
+        let i = self.eg.find_applied_id(i);
+
+        // only e-nodes that have a term within the depth bound.
+        let candidates: Vec<&L> = self.nodes[&i.id]
+            .iter()
+            .filter(|(_, h)| h.is_some_and(|h| h <= depth))
+            .map(|(x, _)| x)
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let weights: Vec<f64> = candidates
+            .iter()
+            .map(|x| (self.weight)(x).max(0.0))
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let idx = if total > 0.0 {
+            let mut r = self.rng.next_f64() * total;
+            let mut idx = weights.len() - 1;
+            for (j, w) in weights.iter().enumerate() {
+                if r < *w {
+                    idx = j;
+                    break;
+                }
+                r -= w;
+            }
+            idx
+        } else {
+            (self.rng.next_u64() % candidates.len() as u64) as usize
+        };
+
+        // rename bound and redundant slots to fresh ones, and the slots of the e-class according to `i`.
+        let x = candidates[idx].refresh_internals(i.m.keys());
+        let mut m = i.m.clone();
+        for s in x.slots() {
+            if !m.contains_key(s) {
+                m.insert(s, s);
+            }
+        }
+        let x = x.apply_slotmap(&m);
+
+        let mut children = Vec::new();
+        for c in x.applied_id_occurrences() {
+            children.push(self.sample_rec(c, depth - 1)?);
+        }
+        Some(RecExpr { node: x, children })
+    }
+}
+
+// A small and fast pseudo-random number generator, see https://prng.di.unimi.it/splitmix64.c
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // uniformly distributed in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,230 @@
+use crate::*;
+
+use std::collections::BinaryHeap;
+
+/// An extractor that keeps the `k` best terms of each e-class, instead of only the best one.
+///
+/// Terms that are equal up to renaming of their bound slots (i.e. alpha-equivalent terms) are only kept once.
+/// Like the [Extractor], this assumes that the [CostFunction] is monotonic in the costs of the children.
+pub struct TopKExtractor<L: Language, CF: CostFunction<L>> {
+    // the candidates of each e-class, written in terms of the slots of the e-class, ordered by cost.
+    map: Candidates<L, CF::Cost>,
+}
+
+impl<L: Language, CF: CostFunction<L>> TopKExtractor<L, CF> {
+    pub fn new<N: Analysis<L>>(eg: &EGraph<L, N>, cost_fn: CF, k: usize) -> Self {
+        if CHECKS {
+            eg.check();
+        }
+
+        let nodes: Vec<(Id, Vec<L>)> = eg
+            .ids()
+            .into_iter()
+            .map(|id| {
+                let nodes = eg
+                    .sorted_enodes(id)
+                    .iter()
+                    .map(|x| eg.class_nf(x))
+                    .collect();
+                (id, nodes)
+            })
+            .collect();
+
+        let mut map: Candidates<L, CF::Cost> = HashMap::default();
+
+        // Iterate until a fixpoint is reached.
+        // A candidate only replaces a worse one, so this terminates even for cyclic e-graphs.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (id, xs) in &nodes {
+                for x in xs {
+                    for (re, c) in best_combinations(x, &map, &cost_fn, k) {
+                        let cands = map.entry(*id).or_default();
+                        if insert_candidate(cands, re, c, k) {
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        Self { map }
+    }
+
+    /// Returns up to `k` pairwise non-alpha-equivalent terms of the e-class `i`, ranked by their cost.
+    pub fn extract<N: Analysis<L>>(
+        &self,
+        i: &AppliedId,
+        eg: &EGraph<L, N>,
+    ) -> Vec<(RecExpr<L>, CF::Cost)> {
+        let i = eg.find_applied_id(i);
+        self.map
+            .get(&i.id)
+            .map(|v| {
+                v.iter()
+                    .map(|(re, c, _)| (rename_free_slots(re, &i.m), c.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+// For each e-class, the candidates together with their alpha-normalized form.
+type Candidates<L, C> = HashMap<Id, Vec<(RecExpr<L>, C, String)>>;
+
+// Returns up to `k` of the cheapest terms with `x` at the top, based on the current candidates of its children.
+fn best_combinations<L: Language, CF: CostFunction<L>>(
+    x: &L,
+    map: &Candidates<L, CF::Cost>,
+    cost_fn: &CF,
+    k: usize,
+) -> Vec<(RecExpr<L>, CF::Cost)> {
+    let children: Vec<&AppliedId> = x.applied_id_occurrences();
+    let mut child_cands = Vec::new();
+    for c in &children {
+        match map.get(&c.id) {
+            Some(v) if !v.is_empty() => child_cands.push(v),
+            _ => return Vec::new(),
+        }
+    }
+
+    // the node as seen by the cost function, where child `j` is called `Id(j)`.
+    let mut numbered = x.clone();
+    for (j, c) in numbered
+        .applied_id_occurrences_mut()
+        .into_iter()
+        .enumerate()
+    {
+        *c = AppliedId::new(Id(j), SlotMap::new());
+    }
+    let cost_of =
+        |idx: &[usize]| cost_fn.cost(&numbered, |j: Id| child_cands[j.0][idx[j.0]].1.clone());
+
+    // Enumerate the combinations of child candidates in ascending cost, starting from the best one.
+    let mut out = Vec::new();
+    let mut seen: HashSet<Vec<usize>> = HashSet::default();
+    let mut heap = BinaryHeap::new();
+    let start = vec![0; children.len()];
+    heap.push(WithOrdRev(start.clone(), cost_of(&start)));
+    seen.insert(start);
+    while let Some(WithOrdRev(idx, c)) = heap.pop() {
+        let re = RecExpr {
+            node: x.clone(),
+            children: children
+                .iter()
+                .enumerate()
+                .map(|(pos, child)| rename_free_slots(&child_cands[pos][idx[pos]].0, &child.m))
+                .collect(),
+        };
+        out.push((re, c));
+        if out.len() >= k {
+            break;
+        }
+
+        for pos in 0..idx.len() {
+            let mut next = idx.clone();
+            next[pos] += 1;
+            if next[pos] < child_cands[pos].len() && seen.insert(next.clone()) {
+                let c = cost_of(&next);
+                heap.push(WithOrdRev(next, c));
+            }
+        }
+    }
+    out
+}
+
+// Inserts the candidate, unless it's alpha-equivalent to an existing one or worse than `k` others.
+// Returns whether the candidates changed.
+fn insert_candidate<L: Language, C: Ord>(
+    cands: &mut Vec<(RecExpr<L>, C, String)>,
+    re: RecExpr<L>,
+    c: C,
+    k: usize,
+) -> bool {
+    if cands.len() >= k && cands.last().is_some_and(|(_, last, _)| c >= *last) {
+        return false;
+    }
+    let key = alpha_normalize(&re);
+    match cands.iter().position(|(_, _, key2)| *key2 == key) {
+        Some(p) if cands[p].1 <= c => return false,
+        Some(p) => {
+            cands.remove(p);
+        }
+        None => {}
+    }
+
+    let pos = cands.partition_point(|(_, c2, _)| *c2 <= c);
+    cands.insert(pos, (re, c, key));
+    cands.truncate(k);
+    true
+}
+
+// Renames all slot occurrences of `re` using `m`, including the bound ones. Slots outside of `m` are kept.
+pub(crate) fn rename_rec_expr<L: Language>(re: &RecExpr<L>, m: &SlotMap) -> RecExpr<L> {
+    let mut node = re.node.clone();
+    for x in node.all_slot_occurrences_mut() {
+        if let Some(y) = m.get(*x) {
+            *x = y;
+        }
+    }
+    RecExpr {
+        node,
+        children: re.children.iter().map(|x| rename_rec_expr(x, m)).collect(),
+    }
+}
+
+// Renames the free slots of `re` using `m`, free slots outside of `m` are kept.
+// The bound slots get fresh names, as the same candidate can occur several times in a term,
+// e.g. inside of itself. Otherwise its binders could capture the slots of the surrounding term.
+pub(crate) fn rename_free_slots<L: Language>(re: &RecExpr<L>, m: &SlotMap) -> RecExpr<L> {
+    let public = re.node.slots();
+    let mut m = m.clone();
+    let mut bound = HashSet::default();
+    for s in re.node.all_slot_occurrences() {
+        if !public.contains(&s) && bound.insert(s) {
+            m.insert(s, Slot::fresh());
+        }
+    }
+    let mut node = re.node.clone();
+    for x in node.all_slot_occurrences_mut() {
+        if let Some(y) = m.get(*x) {
+            *x = y;
+        }
+    }
+    RecExpr {
+        node,
+        children: re
+            .children
+            .iter()
+            .map(|x| rename_free_slots(x, &m))
+            .collect(),
+    }
+}
+
+// Prints `re` with all bound slots renamed to `$0`, `$1`, ... in the order of their binders.
+// Hence two terms are alpha-equivalent iff their normalized forms are equal.
+fn alpha_normalize<L: Language>(re: &RecExpr<L>) -> String {
+    fn rec<L: Language>(re: &RecExpr<L>, m: &SlotMap, counter: &mut u32) -> RecExpr<L> {
+        let public = re.node.slots();
+        let mut m = m.clone();
+        let mut bound = HashSet::default();
+        for s in re.node.all_slot_occurrences() {
+            if !public.contains(&s) && bound.insert(s) {
+                m.insert(s, Slot::numeric(*counter));
+                *counter += 1;
+            }
+        }
+        let mut node = re.node.clone();
+        for x in node.all_slot_occurrences_mut() {
+            if let Some(y) = m.get(*x) {
+                *x = y;
+            }
+        }
+        RecExpr {
+            node,
+            children: re.children.iter().map(|x| rec(x, &m, counter)).collect(),
+        }
+    }
+    rec(re, &SlotMap::new(), &mut 0).to_string()
+}
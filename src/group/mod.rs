@@ -64,6 +64,22 @@ impl<P: Permutation> Group<P> {
         out
     }
 
+    // Iterates over the same perms as `generators`, without collecting them.
+    // The non-identity perms of different layers are distinct, as only those of the upper layer move its `stab`.
+    pub fn generators_iter(&self) -> impl Iterator<Item = &P> {
+        std::iter::successors(self.next.as_deref(), |n| n.g.next.as_deref())
+            .flat_map(|n| n.ot.values())
+            .filter(move |p| **p != self.identity)
+    }
+
+    // Iterates over all perms stored in the stab chain, including the identities of each layer.
+    #[cfg(feature = "explanations")]
+    pub fn stored_perms_iter(&self) -> impl Iterator<Item = &P> {
+        std::iter::successors(Some(self), |g| g.next.as_deref().map(|n| &n.g)).flat_map(|g| {
+            std::iter::once(&g.identity).chain(g.next.iter().flat_map(|n| n.ot.values()))
+        })
+    }
+
     // Should be very rarely called.
     #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
     pub fn all_perms(&self) -> HashSet<P> {
@@ -47,6 +47,9 @@ pub(crate) use parse::*;
 mod lang;
 pub use lang::*;
 
+mod symbol_lang;
+pub use symbol_lang::*;
+
 mod slotmap;
 pub use slotmap::*;
 
@@ -70,5 +73,8 @@ use group::*;
 mod run;
 pub use run::*;
 
+mod command;
+pub use command::*;
+
 #[cfg(feature = "trace")]
 pub(crate) use tracing::instrument;
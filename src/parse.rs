@@ -8,6 +8,12 @@ pub enum ParseError {
     FromSyntaxFailed(Vec<SyntaxElem>),
     ExpectedColonEquals(Vec<Token>),
     ExpectedRBracket(Vec<Token>),
+
+    /// The input ended in the middle of a pattern.
+    UnexpectedEnd,
+
+    /// A term was expected, but a pattern containing `?x` or `b[x := t]` was given.
+    ExpectedTerm(String),
 }
 
 #[derive(Debug, Clone)]
@@ -46,7 +52,7 @@ fn crop_ident(s: &str) -> Result<(/*ident*/ &str, /*rest*/ &str), ParseError> {
     Ok(out)
 }
 
-fn tokenize(mut s: &str) -> Result<Vec<Token>, ParseError> {
+pub(crate) fn tokenize(mut s: &str) -> Result<Vec<Token>, ParseError> {
     let mut tokens = Vec::new();
 
     loop {
@@ -105,18 +111,30 @@ impl<L: Language> Pattern<L> {
 impl<L: Language> RecExpr<L> {
     pub fn parse(s: &str) -> Result<Self, ParseError> {
         let pat = Pattern::parse(s)?;
+        if !is_term(&pat) {
+            return Err(ParseError::ExpectedTerm(pat.to_string()));
+        }
         Ok(pattern_to_re(&pat))
     }
 }
 
-fn parse_pattern<L: Language>(tok: &[Token]) -> Result<(Pattern<L>, &[Token]), ParseError> {
+fn is_term<L: Language>(pat: &Pattern<L>) -> bool {
+    match pat {
+        Pattern::ENode(_, children) => children.iter().all(is_term),
+        _ => false,
+    }
+}
+
+pub(crate) fn parse_pattern<L: Language>(
+    tok: &[Token],
+) -> Result<(Pattern<L>, &[Token]), ParseError> {
     let (mut pat, mut tok) = parse_pattern_nosubst(tok)?;
     while let Some(Token::LBracket) = tok.get(0) {
         tok = &tok[1..];
         let (l, tok2) = parse_pattern(tok)?;
         tok = tok2;
 
-        let Token::ColonEquals = &tok[0] else {
+        let Some(Token::ColonEquals) = tok.first() else {
             return Err(ParseError::ExpectedColonEquals(to_vec(tok)));
         };
         tok = &tok[1..];
@@ -124,7 +142,7 @@ fn parse_pattern<L: Language>(tok: &[Token]) -> Result<(Pattern<L>, &[Token]), P
         let (r, tok2) = parse_pattern(tok)?;
         tok = tok2;
 
-        let Token::RBracket = &tok[0] else {
+        let Some(Token::RBracket) = tok.first() else {
             return Err(ParseError::ExpectedRBracket(to_vec(tok)));
         };
         tok = &tok[1..];
@@ -137,24 +155,30 @@ fn parse_pattern<L: Language>(tok: &[Token]) -> Result<(Pattern<L>, &[Token]), P
 fn parse_pattern_nosubst<L: Language>(
     mut tok: &[Token],
 ) -> Result<(Pattern<L>, &[Token]), ParseError> {
-    if let Token::PVar(p) = &tok[0] {
+    let Some(first) = tok.first() else {
+        return Err(ParseError::UnexpectedEnd);
+    };
+
+    if let Token::PVar(p) = first {
         let pat = Pattern::PVar(p.to_string());
         return Ok((pat, &tok[1..]));
     }
 
-    if let Token::LParen = tok[0] {
+    if let Token::LParen = first {
         tok = &tok[1..];
 
-        let Token::Ident(op) = &tok[0] else {
+        let Some(Token::Ident(op)) = tok.first() else {
             return Err(ParseError::ParseState(to_vec(tok)));
         };
         tok = &tok[1..];
 
         let mut syntax_elems = vec![NestedSyntaxElem::String(op.to_string())];
         loop {
-            if let Token::RParen = tok[0] {
-                break;
-            };
+            match tok.first() {
+                Some(Token::RParen) => break,
+                None => return Err(ParseError::UnexpectedEnd),
+                _ => {}
+            }
 
             let (se, tok2) = parse_nested_syntax_elem(tok)?;
             tok = tok2;
@@ -183,7 +207,7 @@ fn parse_pattern_nosubst<L: Language>(
         let re = Pattern::ENode(node, syntax_elems);
         Ok((re, tok))
     } else {
-        let Token::Ident(op) = &tok[0] else {
+        let Token::Ident(op) = first else {
             return Err(ParseError::ParseState(to_vec(tok)));
         };
         tok = &tok[1..];
@@ -206,7 +230,7 @@ enum NestedSyntaxElem<L: Language> {
 fn parse_nested_syntax_elem<L: Language>(
     tok: &[Token],
 ) -> Result<(NestedSyntaxElem<L>, &[Token]), ParseError> {
-    if let Token::Slot(slot) = &tok[0] {
+    if let Some(Token::Slot(slot)) = tok.first() {
         return Ok((NestedSyntaxElem::Slot(*slot), &tok[1..]));
     }
 
@@ -14,6 +14,9 @@ pub use subst_method::*;
 pub struct Rewrite<L: Language, N: Analysis<L> = ()> {
     pub(crate) searcher: Box<dyn Fn(&EGraph<L, N>) -> Box<dyn Any>>,
     pub(crate) applier: Box<dyn Fn(Box<dyn Any>, &mut EGraph<L, N>)>,
+
+    // the name and both sides of rules built from patterns. Custom rules built from a [RewriteT] don't have these.
+    pub(crate) patterns: Option<(String, Pattern<L>, Pattern<L>)>,
 }
 
 /// Use this type when you want to build your own [Rewrite].
@@ -34,6 +37,7 @@ impl<L: Language + 'static, N: Analysis<L> + 'static, T: 'static> RewriteT<L, N,
         Rewrite {
             searcher: Box::new(move |eg| Box::new((*searcher)(eg))),
             applier: Box::new(move |t, eg| (*applier)(any_to_t(t), eg)),
+            patterns: None,
         }
     }
 }
@@ -48,11 +52,18 @@ fn any_to_t<T: Any>(t: Box<dyn Any>) -> T {
 pub fn apply_rewrites<L: Language, N: Analysis<L>>(
     eg: &mut EGraph<L, N>,
     rewrites: &[Rewrite<L, N>],
+) -> bool {
+    apply_rewrites_iter(eg, rewrites.iter())
+}
+
+pub(crate) fn apply_rewrites_iter<'a, L: Language + 'a, N: Analysis<L> + 'a>(
+    eg: &mut EGraph<L, N>,
+    rewrites: impl Iterator<Item = &'a Rewrite<L, N>> + Clone,
 ) -> bool {
     let prog = eg.progress();
 
-    let ts: Vec<Box<dyn Any>> = rewrites.iter().map(|rw| (*rw.searcher)(eg)).collect();
-    for (rw, t) in rewrites.iter().zip(ts.into_iter()) {
+    let ts: Vec<Box<dyn Any>> = rewrites.clone().map(|rw| (*rw.searcher)(eg)).collect();
+    for (rw, t) in rewrites.zip(ts) {
         (*rw.applier)(t, eg);
     }
 
@@ -74,15 +85,27 @@ impl<L: Language + 'static, N: Analysis<L> + 'static> Rewrite<L, N> {
     ) -> Self {
         let a = Pattern::parse(a).unwrap();
         let b = Pattern::parse(b).unwrap();
+        Self::from_patterns_if(rule, a, b, cond)
+    }
+
+    pub(crate) fn from_patterns_if(
+        rule: &str,
+        a: Pattern<L>,
+        b: Pattern<L>,
+        cond: impl Fn(&Subst, &EGraph<L, N>) -> bool + 'static,
+    ) -> Self {
+        let patterns = Some((rule.to_string(), a.clone(), b.clone()));
         let rule = rule.to_string();
         let a2 = a.clone();
-        RewriteT {
+        let mut rw: Self = RewriteT {
             searcher: Box::new(move |eg| ematch_all(eg, &a)),
             applier: Box::new(move |substs, eg| {
                 Self::apply_substs_cond(substs, &cond, &a2, &b, &rule, eg)
             }),
         }
-        .into()
+        .into();
+        rw.patterns = patterns;
+        rw
     }
 
     #[cfg_attr(feature = "trace", instrument(level = "trace", skip_all))]
@@ -50,6 +50,74 @@ pub fn pattern_subst<L: Language, N: Analysis<L>>(
     }
 }
 
+/// Checks that `lhs => rhs` can be used as a rewrite rule.
+///
+/// Every pattern variable and every free slot of `rhs` has to occur in `lhs`, as the rule otherwise has nothing to instantiate them with.
+/// Substitutions `b[x := t]` are only allowed in `rhs`.
+pub fn check_rule<L: Language>(lhs: &Pattern<L>, rhs: &Pattern<L>) -> Result<(), String> {
+    if has_subst(lhs) {
+        return Err(format!(
+            "the left-hand side `{lhs}` can't contain a substitution"
+        ));
+    }
+
+    let lhs_vars = pattern_vars(lhs);
+    if let Some(v) = pattern_vars(rhs).iter().find(|v| !lhs_vars.contains(*v)) {
+        return Err(format!("`?{v}` is missing in the left-hand side `{lhs}`"));
+    }
+
+    let lhs_slots = pattern_free_slots(lhs);
+    if let Some(s) = pattern_free_slots(rhs)
+        .iter()
+        .find(|s| !lhs_slots.contains(*s))
+    {
+        return Err(format!(
+            "the free slot `{s}` is missing in the left-hand side `{lhs}`"
+        ));
+    }
+    Ok(())
+}
+
+fn has_subst<L: Language>(pat: &Pattern<L>) -> bool {
+    match pat {
+        Pattern::ENode(_, children) => children.iter().any(has_subst),
+        Pattern::PVar(_) => false,
+        Pattern::Subst(..) => true,
+    }
+}
+
+fn pattern_vars<L: Language>(pat: &Pattern<L>) -> HashSet<String> {
+    match pat {
+        Pattern::ENode(_, children) => children.iter().flat_map(pattern_vars).collect(),
+        Pattern::PVar(v) => [v.clone()].into_iter().collect(),
+        Pattern::Subst(b, x, t) => [b, x, t]
+            .into_iter()
+            .flat_map(|p| pattern_vars(p))
+            .collect(),
+    }
+}
+
+// The slots that occur in `pat` without being bound by one of its nodes.
+fn pattern_free_slots<L: Language>(pat: &Pattern<L>) -> HashSet<Slot> {
+    match pat {
+        Pattern::ENode(n, children) => {
+            let public: HashSet<Slot> = n.public_slot_occurrences().into_iter().collect();
+            let bound: HashSet<Slot> = n
+                .all_slot_occurrences()
+                .into_iter()
+                .filter(|s| !public.contains(s))
+                .collect();
+            let inner: HashSet<Slot> = children.iter().flat_map(pattern_free_slots).collect();
+            &public | &(&inner - &bound)
+        }
+        Pattern::PVar(_) => HashSet::default(),
+        // the slots of `x` are replaced by `t`.
+        Pattern::Subst(b, x, t) => {
+            &(&pattern_free_slots(b) - &pattern_free_slots(x)) | &pattern_free_slots(t)
+        }
+    }
+}
+
 // TODO maybe move into EGraph API?
 pub fn lookup_rec_expr<L: Language, N: Analysis<L>>(
     re: &RecExpr<L>,
@@ -15,6 +15,8 @@ pub trait SubstMethod<L: Language, N: Analysis<L>> {
 }
 
 /// A [SubstMethod] that uses the [EGraph::get_syn_expr] of an e-class to do substitution on it.
+///
+/// If explanations are disabled, there is no syntactic term, and it uses the term of [ExtractionSubst] instead.
 pub struct SynExprSubst;
 
 impl<L: Language, N: Analysis<L>> SubstMethod<L, N> for SynExprSubst {
@@ -29,7 +31,10 @@ impl<L: Language, N: Analysis<L>> SubstMethod<L, N> for SynExprSubst {
         t: AppliedId,
         eg: &mut EGraph<L, N>,
     ) -> AppliedId {
-        let term = eg.get_syn_expr(&eg.synify_app_id(b));
+        let term = match eg.get_syn_expr(&eg.synify_app_id(b.clone())) {
+            Some(term) => term,
+            None => ast_size_extract::<L, N>(&b, eg),
+        };
         do_term_subst(eg, &term, &x, &t)
     }
 }
@@ -1,3 +1,5 @@
+use crate::*;
+
 #[derive(Debug, Clone)]
 pub enum StopReason {
     Saturated,
@@ -5,6 +7,10 @@ pub enum StopReason {
     TimeLimit,
     NodeLimit,
     Other(String),
+
+    /// The e-graph merged e-classes with contradicting analysis data, see [Analysis::try_merge].
+    /// This is the first conflict found by the last iteration.
+    AnalysisConflict(AnalysisConflict),
 }
 
 #[derive(Debug)]
@@ -18,8 +18,14 @@ where
     let stop_reason: StopReason;
 
     loop {
+        let conflicts = egraph.analysis_conflicts().len();
         let did_change = apply_rewrites(egraph, &rws);
 
+        if let Some(c) = egraph.analysis_conflicts().get(conflicts) {
+            stop_reason = StopReason::AnalysisConflict(c.clone());
+            break;
+        }
+
         match hook(egraph) {
             Ok(_) => (),
             Err(msg) => {
@@ -164,10 +164,15 @@ where
 
         let mut result = Ok(());
 
-        // Apply rewrites, then check hooks, then check limits, then check if saturated.
+        // Apply rewrites, then check for analysis conflicts, then check hooks, then check limits, then check if saturated.
+        let conflicts = self.egraph.analysis_conflicts().len();
         let progress = apply_rewrites(&mut self.egraph, rewrites);
 
         result = result
+            .and_then(|_| match self.egraph.analysis_conflicts().get(conflicts) {
+                Some(c) => Err(StopReason::AnalysisConflict(c.clone())),
+                None => Ok(()),
+            })
             .and_then(|_| {
                 hooks.iter_mut().try_for_each(|hook| {
                     hook(self).map_err(|err_msg| StopReason::Other(String::from(err_msg)))
@@ -0,0 +1,175 @@
+use crate::*;
+
+/// A [Language] whose operators are only known at runtime.
+///
+/// Any term of the form `(op arg1 arg2 ...)` (or just `op`) parses into a [SymbolLang] e-node.
+/// As the operators carry no type information, the role of each [Slot] is determined by its position:
+/// - A slot that is directly followed by a child binds that slot within the child, like `(lam $x body)` or `(let $x body t)`.
+/// - Every other slot is a public slot, like `(var $x)` or `(f $x $y)`.
+///
+/// This convention agrees with the syntax of [Bind], so languages like the lambda calculus can be expressed without writing any Rust code.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SymbolLang {
+    pub op: Symbol,
+    pub args: Vec<SymbolArg>,
+}
+
+/// An argument of a [SymbolLang] e-node.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SymbolArg {
+    Slot(Slot),
+    Bind(Bind<AppliedId>),
+    AppliedId(AppliedId),
+}
+
+impl SymbolLang {
+    /// Creates an e-node `(op children...)` without any slot arguments.
+    pub fn new(op: &str, children: Vec<AppliedId>) -> Self {
+        SymbolLang {
+            op: Symbol::from(op),
+            args: children.into_iter().map(SymbolArg::AppliedId).collect(),
+        }
+    }
+
+    /// Creates a leaf e-node `op`.
+    pub fn leaf(op: &str) -> Self {
+        Self::new(op, Vec::new())
+    }
+}
+
+impl Language for SymbolLang {
+    fn all_slot_occurrences_mut(&mut self) -> Vec<&mut Slot> {
+        let mut out = Vec::new();
+        for a in &mut self.args {
+            match a {
+                SymbolArg::Slot(s) => out.push(s),
+                SymbolArg::Bind(b) => out.extend(b.all_slot_occurrences_iter_mut()),
+                SymbolArg::AppliedId(x) => out.extend(x.all_slot_occurrences_iter_mut()),
+            }
+        }
+        out
+    }
+
+    fn public_slot_occurrences_mut(&mut self) -> Vec<&mut Slot> {
+        let mut out = Vec::new();
+        for a in &mut self.args {
+            match a {
+                SymbolArg::Slot(s) => out.push(s),
+                SymbolArg::Bind(b) => out.extend(b.public_slot_occurrences_iter_mut()),
+                SymbolArg::AppliedId(x) => out.extend(x.public_slot_occurrences_iter_mut()),
+            }
+        }
+        out
+    }
+
+    fn applied_id_occurrences_mut(&mut self) -> Vec<&mut AppliedId> {
+        let mut out = Vec::new();
+        for a in &mut self.args {
+            match a {
+                SymbolArg::Slot(_) => {}
+                SymbolArg::Bind(b) => out.push(&mut b.elem),
+                SymbolArg::AppliedId(x) => out.push(x),
+            }
+        }
+        out
+    }
+
+    fn all_slot_occurrences(&self) -> Vec<Slot> {
+        let mut out = Vec::new();
+        for a in &self.args {
+            match a {
+                SymbolArg::Slot(s) => out.push(*s),
+                SymbolArg::Bind(b) => out.extend(b.all_slot_occurrences_iter().copied()),
+                SymbolArg::AppliedId(x) => out.extend(x.all_slot_occurrences_iter().copied()),
+            }
+        }
+        out
+    }
+
+    fn public_slot_occurrences(&self) -> Vec<Slot> {
+        let mut out = Vec::new();
+        for a in &self.args {
+            match a {
+                SymbolArg::Slot(s) => out.push(*s),
+                SymbolArg::Bind(b) => out.extend(b.public_slot_occurrences_iter().copied()),
+                SymbolArg::AppliedId(x) => out.extend(x.public_slot_occurrences_iter().copied()),
+            }
+        }
+        out
+    }
+
+    fn applied_id_occurrences(&self) -> Vec<&AppliedId> {
+        let mut out = Vec::new();
+        for a in &self.args {
+            match a {
+                SymbolArg::Slot(_) => {}
+                SymbolArg::Bind(b) => out.push(&b.elem),
+                SymbolArg::AppliedId(x) => out.push(x),
+            }
+        }
+        out
+    }
+
+    fn to_syntax(&self) -> Vec<SyntaxElem> {
+        let mut out = vec![SyntaxElem::String(self.op.to_string())];
+        for a in &self.args {
+            match a {
+                SymbolArg::Slot(s) => out.extend(s.to_syntax()),
+                SymbolArg::Bind(b) => out.extend(b.to_syntax()),
+                SymbolArg::AppliedId(x) => out.extend(x.to_syntax()),
+            }
+        }
+        out
+    }
+
+    fn from_syntax(elems: &[SyntaxElem]) -> Option<Self> {
+        let SyntaxElem::String(op) = elems.first()? else {
+            return None;
+        };
+
+        let mut args = Vec::new();
+        let mut rest = &elems[1..];
+        while let Some(e) = rest.first() {
+            match (e, rest.get(1)) {
+                (SyntaxElem::Slot(s), Some(SyntaxElem::AppliedId(x))) => {
+                    args.push(SymbolArg::Bind(Bind {
+                        slot: *s,
+                        elem: x.clone(),
+                    }));
+                    rest = &rest[2..];
+                }
+                (SyntaxElem::Slot(s), _) => {
+                    args.push(SymbolArg::Slot(*s));
+                    rest = &rest[1..];
+                }
+                (SyntaxElem::AppliedId(x), _) => {
+                    args.push(SymbolArg::AppliedId(x.clone()));
+                    rest = &rest[1..];
+                }
+                (SyntaxElem::String(_), _) => return None,
+            }
+        }
+
+        Some(SymbolLang {
+            op: Symbol::from(&**op),
+            args,
+        })
+    }
+
+    fn slots(&self) -> SmallHashSet<Slot> {
+        self.public_slot_occurrences().into_iter().collect()
+    }
+
+    fn weak_shape_inplace(&mut self) -> SlotMap {
+        let m = &mut (SlotMap::new(), 0);
+        for a in &mut self.args {
+            match a {
+                SymbolArg::Slot(s) => s.weak_shape_impl(m),
+                SymbolArg::Bind(b) => b.weak_shape_impl(m),
+                SymbolArg::AppliedId(x) => x.weak_shape_impl(m),
+            }
+        }
+
+        m.0.inverse()
+    }
+}
@@ -0,0 +1,158 @@
+use crate::*;
+
+// constant folding, as a component of a bigger analysis.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Folded(Option<u32>);
+
+impl<N: Has<Folded, I>, I> AnalysisPart<Arith, N, I> for Folded {
+    fn make(eg: &EGraph<Arith, N>, enode: &Arith) -> Folded
+    where
+        N: Analysis<Arith>,
+    {
+        let get = |x: &AppliedId| eg.analysis_part::<Folded, I>(x.id).0;
+        Folded(match enode {
+            Arith::Number(x) => Some(*x),
+            Arith::Add(x, y) => get(x).zip(get(y)).map(|(x, y)| x + y),
+            Arith::Mul(x, y) => get(x).zip(get(y)).map(|(x, y)| x * y),
+            _ => None,
+        })
+    }
+
+    fn merge(&mut self, other: Folded) -> DidMerge {
+        match (self.0, other.0) {
+            (None, Some(_)) => {
+                *self = other;
+                DidMerge(true, false)
+            }
+            (Some(_), None) => DidMerge(false, true),
+            _ => DidMerge(false, false),
+        }
+    }
+}
+
+// the height of the smallest term, where terms that fold to a constant have height zero.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Height(u32);
+
+// It reads the data of `Folded`, so it's only implemented for this particular combination.
+type FoldedHeight = (Folded, Height);
+
+impl AnalysisPart<Arith, FoldedHeight, InPart<1, Here>> for Height {
+    fn make(eg: &EGraph<Arith, FoldedHeight>, enode: &Arith) -> Height {
+        let height = |x: &AppliedId| match eg.analysis_part::<Folded, _>(x.id).0 {
+            Some(_) => 0,
+            None => eg.analysis_part::<Height, _>(x.id).0,
+        };
+        let children = enode.applied_id_occurrences().into_iter().map(height).max();
+        Height(children.map(|h| h + 1).unwrap_or(0))
+    }
+
+    fn merge(&mut self, other: Height) -> DidMerge {
+        let old = self.0;
+        self.0 = self.0.min(other.0);
+        DidMerge(self.0 != old, self.0 != other.0)
+    }
+}
+
+#[test]
+fn analysis_tuple() {
+    let mut eg = EGraph::<Arith, FoldedHeight>::new();
+    let i = eg.add_expr(RecExpr::parse("(add (mul 2 3) (add x 1))").unwrap());
+    let j = eg.add_expr(RecExpr::parse("(mul 2 3)").unwrap());
+
+    assert_eq!(eg.analysis_part::<Folded, _>(j.id), &Folded(Some(6)));
+    assert_eq!(eg.analysis_part::<Folded, _>(i.id), &Folded(None));
+    assert_eq!(eg.analysis_part::<Height, _>(i.id), &Height(2));
+
+    // once x is known, everything folds.
+    let x = eg.add_expr(RecExpr::parse("x").unwrap());
+    let four = eg.add_expr(RecExpr::parse("4").unwrap());
+    eg.union(&x, &four);
+    assert_eq!(eg.analysis_data(i.id), &(Folded(Some(11)), Height(1)));
+}
+
+#[test]
+fn analysis_part_mut() {
+    let mut eg = EGraph::<Arith, FoldedHeight>::new();
+    let i = eg.add_expr(RecExpr::parse("(add x 1)").unwrap());
+    let x = eg.add_expr(RecExpr::parse("x").unwrap());
+
+    *eg.analysis_part_mut::<Folded, _>(x.id) = Folded(Some(2));
+    eg.reanalyze([x.id]);
+    assert_eq!(eg.analysis_part::<Folded, _>(i.id), &Folded(Some(3)));
+}
+
+// constant folding that reports different constants as a conflict.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Exact(Option<u32>);
+
+impl<N: Has<Exact, I>, I> AnalysisPart<Arith, N, I> for Exact {
+    fn make(eg: &EGraph<Arith, N>, enode: &Arith) -> Exact
+    where
+        N: Analysis<Arith>,
+    {
+        let get = |x: &AppliedId| eg.analysis_part::<Exact, I>(x.id).0;
+        Exact(match enode {
+            Arith::Number(x) => Some(*x),
+            Arith::Add(x, y) => get(x).zip(get(y)).map(|(x, y)| x + y),
+            _ => None,
+        })
+    }
+
+    fn merge(&mut self, other: Exact) -> DidMerge {
+        <Exact as AnalysisPart<Arith, N, I>>::try_merge(self, other).unwrap()
+    }
+
+    fn try_merge(&mut self, other: Exact) -> Result<DidMerge, String> {
+        match (self.0, other.0) {
+            (Some(x), Some(y)) if x != y => Err(format!("{x} != {y}")),
+            (None, Some(_)) => {
+                *self = other;
+                Ok(DidMerge(true, false))
+            }
+            (_, None) => Ok(DidMerge(false, other.0 != self.0)),
+            _ => Ok(DidMerge(false, false)),
+        }
+    }
+}
+
+// the size of the smallest term.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Size(u32);
+
+impl<N: Has<Size, I>, I> AnalysisPart<Arith, N, I> for Size {
+    fn make(eg: &EGraph<Arith, N>, enode: &Arith) -> Size
+    where
+        N: Analysis<Arith>,
+    {
+        let children = enode.applied_id_occurrences();
+        Size(
+            1 + children
+                .iter()
+                .map(|x| eg.analysis_part::<Size, I>(x.id).0)
+                .sum::<u32>(),
+        )
+    }
+
+    fn merge(&mut self, other: Size) -> DidMerge {
+        let old = self.0;
+        self.0 = self.0.min(other.0);
+        DidMerge(self.0 != old, self.0 != other.0)
+    }
+}
+
+#[test]
+fn analysis_tuple_conflict() {
+    let mut eg = EGraph::<Arith, (Size, Exact)>::new();
+    let a = eg.add_expr(RecExpr::parse("(add 1 2)").unwrap());
+    let b = eg.add_expr(RecExpr::parse("4").unwrap());
+    let p = eg.add_expr(RecExpr::parse("(mul (add 1 2) x)").unwrap());
+    assert_eq!(eg.analysis_part::<Size, _>(p.id), &Size(5));
+
+    // only the conflicting component keeps its old data, the other one is merged and propagated.
+    eg.union(&a, &b);
+    assert_eq!(eg.analysis_conflicts().len(), 1);
+    assert_eq!(eg.analysis_part::<Size, _>(a.id), &Size(1));
+    assert_eq!(eg.analysis_part::<Size, _>(p.id), &Size(3));
+    assert_ne!(eg.analysis_part::<Exact, _>(a.id), &Exact(None));
+}
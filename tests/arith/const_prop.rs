@@ -4,15 +4,20 @@ use crate::*;
 pub struct ConstProp(Option<u32>);
 
 impl Analysis<Arith> for ConstProp {
-    fn merge(x: ConstProp, y: ConstProp) -> ConstProp {
-        match (x.0, y.0) {
-            (Some(x), Some(y)) => {
-                assert_eq!(x, y);
-                ConstProp(Some(x))
+    fn merge(&mut self, other: ConstProp) -> DidMerge {
+        self.try_merge(other).unwrap()
+    }
+
+    fn try_merge(&mut self, other: ConstProp) -> Result<DidMerge, String> {
+        match (self.0, other.0) {
+            (Some(x), Some(y)) if x != y => Err(format!("{x} != {y}")),
+            (Some(_), Some(_)) => Ok(DidMerge(false, false)),
+            (Some(_), None) => Ok(DidMerge(false, true)),
+            (None, Some(_)) => {
+                *self = other;
+                Ok(DidMerge(true, false))
             }
-            (Some(x), _) => ConstProp(Some(x)),
-            (_, Some(x)) => ConstProp(Some(x)),
-            (_, _) => ConstProp(None),
+            (None, None) => Ok(DidMerge(false, false)),
         }
     }
 
@@ -49,3 +54,48 @@ fn const_prop_union() {
 
     assert_eq!(eg.analysis_data(a.id), &ConstProp(Some(42)));
 }
+
+#[test]
+fn const_prop_conflict() {
+    // claims 1 + 2 = 1.
+    let unsound = Rewrite::new("unsound", "(add ?a ?b)", "?a");
+
+    let mut runner = Runner::<Arith, ConstProp, ()>::new().with_iter_limit(5);
+    runner
+        .egraph
+        .add_syn_expr(RecExpr::parse("(add 1 2)").unwrap());
+    let report = runner.run(&[unsound]);
+
+    let StopReason::AnalysisConflict(c) = &report.stop_reason else {
+        panic!(
+            "expected an analysis conflict, got {:?}",
+            report.stop_reason
+        );
+    };
+    assert!(c.reason == "3 != 1" || c.reason == "1 != 3");
+    assert_eq!(report.iterations, 1);
+    assert_eq!(runner.egraph.analysis_conflicts()[0].reason, c.reason);
+
+    #[cfg(feature = "explanations")]
+    assert!(c
+        .proof
+        .as_ref()
+        .unwrap()
+        .to_string(&runner.egraph)
+        .contains("unsound"));
+}
+
+#[test]
+fn const_prop_reanalyze() {
+    let mut eg = EGraph::<Arith, ConstProp>::new();
+    let i = eg.add_expr(RecExpr::parse("(mul (add a 2) 3)").unwrap());
+    let a = eg.add_expr(RecExpr::parse("a").unwrap());
+    assert_eq!(eg.analysis_data(i.id), &ConstProp(None));
+
+    // changing the data alone doesn't reach the parents.
+    *eg.analysis_data_mut(a.id) = ConstProp(Some(1));
+    assert_eq!(eg.analysis_data(i.id), &ConstProp(None));
+
+    eg.reanalyze([a.id]);
+    assert_eq!(eg.analysis_data(i.id), &ConstProp(Some(9)));
+}
@@ -15,6 +15,9 @@ pub use my_cost::*;
 mod const_prop;
 pub use const_prop::*;
 
+mod analysis_part;
+pub use analysis_part::*;
+
 define_language! {
     pub enum Arith {
         // lambda calculus:
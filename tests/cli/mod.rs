@@ -0,0 +1,96 @@
+use crate::*;
+use std::process::Command;
+
+// runs `slotted TERM_FILE RULE_FILE` and returns whether it succeeded, together with its stderr.
+fn run_cli(name: &str, term: &str, rules: &str) -> (bool, String) {
+    let dir = std::env::temp_dir().join(format!("slotted-cli-{}-{name}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let term_file = dir.join("term");
+    let rule_file = dir.join("rules");
+    std::fs::write(&term_file, term).unwrap();
+    std::fs::write(&rule_file, rules).unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_slotted"))
+        .arg(&term_file)
+        .arg(&rule_file)
+        .arg("--iter-limit")
+        .arg("3")
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    (out.status.success(), String::from_utf8(out.stderr).unwrap())
+}
+
+fn assert_rejected(name: &str, term: &str, rules: &str, msg: &str) {
+    let (ok, err) = run_cli(name, term, rules);
+    assert!(!ok);
+    assert!(!err.contains("panicked"), "{err}");
+    assert!(err.contains(msg), "{err}");
+}
+
+#[test]
+fn cli_runs() {
+    let (ok, err) = run_cli("runs", "(add x 0)", "add-zero: (add ?a 0) => ?a\n");
+    assert!(ok, "{err}");
+}
+
+#[test]
+fn cli_malformed_input() {
+    assert_rejected(
+        "term",
+        "(add x",
+        "add-zero: (add ?a 0) => ?a\n",
+        "can't parse term",
+    );
+    assert_rejected(
+        "pattern",
+        "(add x 0)",
+        "add-zero: (add ?a 0 => ?a\n",
+        "line 1",
+    );
+    assert_rejected(
+        "subst",
+        "(add x 0)",
+        "bad: (f ?a) => ?a[(var $x) :=\n",
+        "line 1",
+    );
+    assert_rejected(
+        "nameless",
+        "(add x 0)",
+        "(app (lam $x ?b) ?t) => ?b[(var $x) := ?t]\n",
+        "expected `name: lhs => rhs`",
+    );
+}
+
+#[test]
+fn cli_unbound_pattern_var() {
+    assert_rejected("pvar", "(f x)", "bad: (f ?a) => ?b\n", "`?b` is missing");
+
+    // in the reverse direction, `?b` would be unbound.
+    assert_rejected(
+        "pvar-rev",
+        "(f x)",
+        "bad: (f ?a ?b) <=> (g ?a)\n",
+        "reverse",
+    );
+}
+
+#[test]
+fn cli_new_free_slot() {
+    assert_rejected(
+        "slot",
+        "(f x)",
+        "bad: (f ?a) => (g (var $z))\n",
+        "free slot `$z`",
+    );
+}
+
+#[test]
+fn check_rule_binders() {
+    let p = |s: &str| Pattern::<SymbolLang>::parse(s).unwrap();
+    assert!(check_rule(&p("(app (lam $x ?b) ?t)"), &p("?b[(var $x) := ?t]")).is_ok());
+    assert!(check_rule(&p("(lam $x (f (var $x)))"), &p("(lam $y (f (var $y)))")).is_ok());
+    assert!(check_rule(&p("(f (var $x))"), &p("(g (var $x))")).is_ok());
+    assert!(check_rule(&p("(lam $x (f (var $x)))"), &p("(f (var $x))")).is_err());
+    assert!(check_rule(&p("?a[(var $x) := ?b]"), &p("?a")).is_err());
+}
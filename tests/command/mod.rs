@@ -0,0 +1,81 @@
+use crate::*;
+
+#[test]
+fn command_script() {
+    let script = "
+        ; beta reduction.
+        (rule beta (app (lam $x ?b) ?t) ?b[(var $x) := ?t])
+        (rule add-zero (add ?a 0) ?a)
+        (add (app (lam $x (add (var $x) 0)) 3))
+        (run 5)
+        (check-eq (app (lam $x (add (var $x) 0)) 3) 3)
+        (extract (app (lam $x (add (var $x) 0)) 3))
+    ";
+    let mut interp = Interpreter::<SymbolLang>::new();
+    let out = interp.run_script(script).unwrap();
+    assert_eq!(out.lines().last(), Some("3"));
+}
+
+#[test]
+fn command_union_and_check() {
+    let mut interp = Interpreter::<SymbolLang>::new();
+    interp.run_script("(union a b)").unwrap();
+    interp.run_script("(check-eq (f a) (f b))").unwrap();
+    assert!(matches!(
+        interp.run_script("(check-eq a c)"),
+        Err(CommandError::CheckFailed(_))
+    ));
+}
+
+#[test]
+fn command_errors() {
+    let mut interp = Interpreter::<SymbolLang>::new();
+    assert!(matches!(
+        interp.run_script("(frobnicate a)"),
+        Err(CommandError::UnknownCommand(_))
+    ));
+    assert!(matches!(
+        interp.run_script("(add (f a)"),
+        Err(CommandError::UnbalancedParens)
+    ));
+    assert!(matches!(
+        interp.run_script("(run 3 missing)"),
+        Err(CommandError::UnknownRule(_))
+    ));
+    assert!(matches!(
+        interp.run_script("(add ?x)"),
+        Err(CommandError::WrongArguments(_))
+    ));
+}
+
+#[test]
+fn command_invalid_rules() {
+    let mut interp = Interpreter::<SymbolLang>::new();
+    interp.run_script("(add (f a))").unwrap();
+    for rule in [
+        "(rule bad (f ?a) ?b)",
+        "(rule bad (f ?a) (g (var $z)))",
+        "(rule bad (f ?a) (g ?a)",
+    ] {
+        assert!(interp.run_script(rule).is_err(), "{rule}");
+    }
+    assert!(matches!(
+        interp.run_script("(rule bad (f ?a) ?b)"),
+        Err(CommandError::WrongArguments(_))
+    ));
+
+    // the session survives, and the rejected rule is unknown.
+    assert!(matches!(
+        interp.run_script("(run 1 bad)"),
+        Err(CommandError::UnknownRule(_))
+    ));
+    assert!(matches!(
+        interp.execute(Command::Rule(
+            "bad".to_string(),
+            Pattern::parse("(f ?a)").unwrap(),
+            Pattern::parse("?b").unwrap(),
+        )),
+        Err(CommandError::WrongArguments(_))
+    ));
+    interp.run_script("(check-eq (f a) (f a))").unwrap();
+}
@@ -0,0 +1,36 @@
+use crate::*;
+
+#[test]
+fn dot_export() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f $x $y)"));
+    let b = eg.add_expr(term("(f $y $x)"));
+    eg.union(&a, &b);
+    let c = eg.add_expr(term("(g (f $x $y) (lam $z (var $z)))"));
+
+    let dot = eg.to_dot(&DotOptions::default());
+    assert!(dot.starts_with("digraph egraph {"));
+    assert!(dot.contains("symmetry: "));
+    assert!(dot.contains("lhead=cluster_"));
+
+    // `(var $z)` is two steps away from `c`, so it is cut off.
+    let dot = eg.to_dot(&DotOptions::default().with_roots([c.id]).with_max_depth(1));
+    let v = eg
+        .lookup(&SymbolLang {
+            op: Symbol::from("var"),
+            args: vec![SymbolArg::Slot(Slot::named("z"))],
+        })
+        .unwrap();
+    assert!(!dot.contains(&format!("subgraph cluster_{} ", v.id.0)));
+    assert!(dot.contains("style=dashed"));
+}
+
+#[test]
+fn dot_redundant_slots() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f $x $y)"));
+    let b = eg.add_expr(term("(f $x $z)"));
+    eg.union(&a, &b);
+    let dot = eg.to_dot(&DotOptions::default().with_roots([a.id]));
+    assert!(dot.contains("redundant: "));
+}
@@ -0,0 +1,79 @@
+use crate::*;
+
+fn build() -> EGraph<Arith, ConstProp> {
+    let mut eg = EGraph::new();
+    eg.add_expr(term("(add 1 2)"));
+    let b = eg.add_expr(term("(add (var $x) (var $y))"));
+    let c = eg.add_expr(term("(add (var $y) (var $x))"));
+    eg.union(&b, &c);
+    eg.add_expr(term("(mul (add 1 2) 3)"));
+    eg
+}
+
+fn dump_string(eg: &EGraph<Arith, ConstProp>) -> String {
+    let mut out = Vec::new();
+    eg.dump_to(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn dump_contents() {
+    let eg = build();
+    let s = dump_string(&eg);
+    assert_eq!(s.contains(">> "), eg.explanations_enabled());
+    assert!(s.contains(" -- "));
+    assert!(s.contains("analysis: ConstProp(Some(3))"));
+    assert!(s.contains("analysis: ConstProp(Some(9))"));
+    assert!(s.contains("analysis: ConstProp(None)"));
+}
+
+#[test]
+fn dump_deterministic() {
+    let eg = build();
+    let s = dump_string(&eg);
+    assert_eq!(s, dump_string(&eg));
+
+    // the Debug output leaves out the analysis data.
+    let without_analysis: Vec<&str> = s
+        .lines()
+        .filter(|l| !l.starts_with(" analysis: "))
+        .collect();
+    assert_eq!(format!("{eg:?}"), without_analysis.join("\n") + "\n");
+
+    let headers: Vec<&str> = s.lines().filter(|l| l.starts_with("id")).collect();
+    assert_eq!(headers.len(), eg.ids().len());
+    for (h, i) in headers.iter().zip(eg.ids()) {
+        assert!(h.starts_with(&format!("{i:?}(")));
+    }
+}
+
+// slot names come from a thread-local counter, but mustn't show up in the dump.
+#[test]
+fn dump_independent_of_fresh_slots() {
+    let build = || {
+        let mut eg = EGraph::<SymbolLang>::new();
+        let a = eg.add_expr(term("(lam $x (app (var $x) (var $y)))"));
+        let b = eg.add_expr(term("(lam $z (app (var $z) (var $y)))"));
+        let c = eg.add_expr(term("(f (var $y) (var $w))"));
+        let d = eg.add_expr(term("(f (var $w) (var $y))"));
+        eg.union(&c, &d);
+        let e = eg.add_expr(term("(g (var $y) (var $w))"));
+        eg.union(&e, &c);
+        eg.union(&a, &b);
+        eg
+    };
+    let dump = |eg: &EGraph<SymbolLang>| {
+        let mut out = Vec::new();
+        eg.dump_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    let a = build();
+    for _ in 0..5 {
+        Slot::fresh();
+    }
+    let b = build();
+    assert_eq!(dump(&a), dump(&b));
+    assert_eq!(format!("{a:?}"), format!("{b:?}"));
+    assert!(!dump(&a).contains("$f"));
+}
@@ -0,0 +1,72 @@
+use crate::*;
+
+fn slot_set(it: impl Iterator<Item = Slot>) -> SmallHashSet<Slot> {
+    it.collect()
+}
+
+#[test]
+fn eclass_symmetry() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f $x $y $z)"));
+    let b = eg.add_expr(term("(f $y $x $z)"));
+    eg.union(&a, &b);
+
+    let c = eg.eclass(a.id);
+    assert_eq!(c.id(), a.id);
+    assert_eq!(slot_set(c.slots()), eg.slots(a.id));
+    assert_eq!(c.symmetry_order(), 2);
+    assert_eq!(c.symmetry_generators().count(), 1);
+    assert!(c.symmetry_generators().all(|g| g.is_perm()));
+
+    let [x, y, z] =
+        [Slot::named("x"), Slot::named("y"), Slot::named("z")].map(|s| a.m.inverse()[s]);
+    assert_eq!(slot_set(c.orbit(x)), slot_set([x, y].into_iter()));
+    assert_eq!(slot_set(c.orbit(z)), singleton_set(z));
+    assert_eq!(c.redundant_slots().count(), 0);
+}
+
+#[test]
+fn eclass_redundant_slots() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f $x $y)"));
+    let b = eg.add_expr(term("(f $x $z)"));
+    eg.union(&a, &b);
+
+    let c = eg.eclass(a.id);
+    assert_eq!(c.slots().count(), 1);
+    let red: Vec<Slot> = c.redundant_slots().collect();
+    assert_eq!(red.len(), 1);
+    assert_eq!(c.syn_enode().is_some(), eg.explanations_enabled());
+    if let Some(syn) = c.syn_enode() {
+        assert!(syn.slots().contains(&red[0]));
+    }
+    assert!(!c.slots().any(|s| s == red[0]));
+}
+
+// without the `explanations` feature, asking for explanations has no effect.
+#[test]
+fn eclass_syn_requires_explanations() {
+    let mut eg = EGraph::<SymbolLang>::new().with_explanations(true);
+    let a = eg.add_syn_expr(term("(f $x)"));
+    let recorded = cfg!(feature = "explanations");
+    assert_eq!(eg.explanations_enabled(), recorded);
+    assert_eq!(eg.get_syn_node(&a).is_some(), recorded);
+    assert_eq!(eg.get_syn_expr(&a).is_some(), recorded);
+}
+
+#[test]
+fn eclass_usages() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let x = eg.add_expr(term("(var $x)"));
+    let f = eg.add_expr(term("(f (var $x) (var $x))"));
+    let g = eg.add_expr(term("(g (var $x))"));
+
+    let c = eg.eclass(x.id);
+    let mut parents: Vec<Id> = c.usages().map(|(i, _)| i).collect();
+    parents.sort();
+    assert_eq!(parents, vec![f.id, g.id]);
+    for (i, n) in c.usages() {
+        assert!(eg.enodes(i).contains(&n));
+    }
+    assert_eq!(eg.eclass(f.id).usages().count(), 0);
+}
@@ -24,6 +24,26 @@ pub use array::*;
 
 mod misc;
 
+mod symbol_lang;
+
+mod command;
+
+mod cli;
+
+mod dot;
+
+mod dump;
+
+mod eclass;
+
+mod extract;
+
+#[cfg(feature = "explanations")]
+mod explain;
+
+#[cfg(feature = "serialize")]
+mod serialize;
+
 pub fn singleton_set<T: Eq + Hash + Ord>(t: T) -> SmallHashSet<T> {
     [t].into_iter().collect()
 }
@@ -0,0 +1,181 @@
+use crate::*;
+
+fn arith_rules() -> Vec<Rewrite<SymbolLang>> {
+    vec![
+        Rewrite::new("add-comm", "(add ?a ?b)", "(add ?b ?a)"),
+        Rewrite::new("add-assoc", "(add ?a (add ?b ?c))", "(add (add ?a ?b) ?c)"),
+        Rewrite::new("mul-zero", "(mul ?a 0)", "0"),
+    ]
+}
+
+fn arith_proof() -> (Vec<Rewrite<SymbolLang>>, ExportedProof<SymbolLang>) {
+    let rules = arith_rules();
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = term("(add (var $x) (add (mul (var $y) 0) (var $z)))");
+    let b = term("(add (add (var $z) 0) (var $x))");
+    eg.add_syn_expr(a.clone());
+    eg.add_syn_expr(b.clone());
+    for _ in 0..4 {
+        apply_rewrites(&mut eg, &rules);
+    }
+    let p = eg.explain_equivalence(a, b);
+    let exported = p.export(&eg);
+    (rules, exported)
+}
+
+#[test]
+fn checker_accepts_rule_instances() {
+    let (rules, proof) = arith_proof();
+    let checker = ProofChecker::from_rewrites(&rules);
+    let a = term("(add (var $x) (add (mul (var $y) 0) (var $z)))");
+    let b = term("(add (add (var $z) 0) (var $x))");
+    assert_eq!(checker.check(&proof, &a, &b), Ok(()));
+
+    // the free slots may be renamed consistently.
+    let a2 = term("(add (var $u) (add (mul (var $v) 0) (var $w)))");
+    let b2 = term("(add (add (var $w) 0) (var $u))");
+    assert_eq!(checker.check(&proof, &a2, &b2), Ok(()));
+
+    // but not independently on both sides.
+    let b3 = term("(add (add (var $x) 0) (var $z))");
+    let err = checker.check(&proof, &a, &b3).unwrap_err();
+    assert_eq!(err.step, proof.root);
+}
+
+#[test]
+fn checker_accepts_binders() {
+    let rules = [
+        Rewrite::new("beta", "(app (lam $x ?b) ?t)", "?b[(var $x) := ?t]"),
+        Rewrite::new("add-zero", "(add ?a 0)", "?a"),
+    ];
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = term("(lam $y (app (lam $x (add (var $x) 0)) (var $y)))");
+    let b = term("(lam $z (var $z))");
+    eg.add_syn_expr(a.clone());
+    for _ in 0..3 {
+        apply_rewrites(&mut eg, &rules);
+    }
+    let p = eg.explain_equivalence(a.clone(), b.clone());
+    let proof = p.export(&eg);
+    assert!(proof
+        .steps
+        .iter()
+        .any(|s| s.rule == ProofRule::Explicit(Some("beta".to_string())) && s.subst.is_some()));
+
+    let checker = ProofChecker::from_rewrites(&rules);
+    assert_eq!(checker.check(&proof, &a, &b), Ok(()));
+}
+
+#[test]
+fn checker_rejects_wrong_substitution() {
+    let rules = [Rewrite::new(
+        "beta",
+        "(app (lam $x ?b) ?t)",
+        "?b[(var $x) := ?t]",
+    )];
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = term("(app (lam $x (add (var $x) (var $x))) (var $y))");
+    let b = term("(add (var $y) (var $y))");
+    eg.add_syn_expr(a.clone());
+    apply_rewrites(&mut eg, &rules);
+    let proof = eg.explain_equivalence(a.clone(), b.clone()).export(&eg);
+    let checker = ProofChecker::from_rewrites(&rules);
+    assert_eq!(checker.check(&proof, &a, &b), Ok(()));
+
+    // a forged beta step with an unrelated right-hand side.
+    // Everything else about the proof is consistent, so only the rule check can catch it.
+    let i = proof
+        .steps
+        .iter()
+        .position(|s| s.rule == ProofRule::Explicit(Some("beta".to_string())))
+        .unwrap();
+    let mut broken = proof.clone();
+    broken.steps[i].rhs.children[1] = term("0");
+    let err = checker
+        .check(&broken, &a, &term("(add (var $y) 0)"))
+        .unwrap_err();
+    assert_eq!(err.step, i);
+    assert!(err.reason.contains("not an instance of `beta`"));
+}
+
+#[test]
+fn checker_rejects_unjustified_steps() {
+    let (rules, proof) = arith_proof();
+    let a = term("(add (var $x) (add (mul (var $y) 0) (var $z)))");
+    let b = term("(add (add (var $z) 0) (var $x))");
+
+    // a rule is missing.
+    let checker = ProofChecker::from_rewrites(&rules[..2]);
+    let err = checker.check(&proof, &a, &b).unwrap_err();
+    assert!(err.reason.contains("mul-zero"));
+
+    // a step claims to apply a rule, but it isn't an instance of it.
+    let checker = ProofChecker::from_rewrites(&rules);
+    let i = proof
+        .steps
+        .iter()
+        .position(|s| s.rule == ProofRule::Explicit(Some("mul-zero".to_string())))
+        .unwrap();
+    let mut broken = proof.clone();
+    broken.steps[i].rule = ProofRule::Explicit(Some("add-comm".to_string()));
+    assert_eq!(checker.check(&broken, &a, &b).unwrap_err().step, i);
+
+    // the recorded substitution doesn't fit.
+    let mut broken = proof.clone();
+    for t in broken.steps[i].subst.as_mut().unwrap().values_mut() {
+        *t = term("(mul 0 0)");
+    }
+    assert_eq!(checker.check(&broken, &a, &b).unwrap_err().step, i);
+
+    // an axiom without justification, as created by `union`.
+    let mut broken = proof.clone();
+    broken.steps[i].rule = ProofRule::Explicit(None);
+    assert_eq!(checker.check(&broken, &a, &b).unwrap_err().step, i);
+}
+
+#[test]
+fn checker_rejects_captured_slots() {
+    // `$y` is free on the left, but bound on the right.
+    let a = term("(lam $x (app (var $x) (var $y)))");
+    let b = term("(lam $y (app (var $y) (var $y)))");
+    let step = |lhs: &RecExpr<SymbolLang>, rhs: &RecExpr<SymbolLang>| ProofStep {
+        lhs: lhs.clone(),
+        rhs: rhs.clone(),
+        rule: ProofRule::Reflexivity,
+        children: Vec::new(),
+        renamings: Vec::new(),
+        subst: None,
+    };
+    let checker = ProofChecker::<SymbolLang>::new();
+
+    let forged = ExportedProof {
+        steps: vec![step(&a, &b)],
+        root: 0,
+    };
+    assert!(forged.check().is_err());
+    assert!(checker.check(&forged, &a, &b).is_err());
+
+    // renaming the binder alone is fine.
+    let c = term("(lam $z (app (var $z) (var $y)))");
+    let renamed = ExportedProof {
+        steps: vec![step(&a, &c)],
+        root: 0,
+    };
+    assert_eq!(checker.check(&renamed, &a, &c), Ok(()));
+
+    // a valid proof doesn't match the captured equation either.
+    assert!(checker.check(&renamed, &a, &b).is_err());
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn checker_after_json_roundtrip() {
+    let (rules, proof) = arith_proof();
+    let imported = ExportedProof::<SymbolLang>::from_json(&proof.to_json()).unwrap();
+    assert_eq!(imported, proof);
+
+    let a = term("(add (var $x) (add (mul (var $y) 0) (var $z)))");
+    let b = term("(add (add (var $z) 0) (var $x))");
+    let checker = ProofChecker::from_rewrites(&rules);
+    assert_eq!(checker.check(&imported, &a, &b), Ok(()));
+}
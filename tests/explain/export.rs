@@ -0,0 +1,184 @@
+use crate::*;
+
+fn lambda_proof() -> (EGraph<SymbolLang>, ProvenEq) {
+    let rules = [
+        Rewrite::new("beta", "(app (lam $x ?b) ?t)", "?b[(var $x) := ?t]"),
+        Rewrite::new("add-zero", "(add ?a 0)", "?a"),
+    ];
+    let mut eg = EGraph::<SymbolLang>::new();
+    eg.add_syn_expr(term("(lam $y (app (lam $x (add (var $x) 0)) (var $y)))"));
+    for _ in 0..3 {
+        apply_rewrites(&mut eg, &rules);
+    }
+    let p = eg.explain_equivalence(
+        term("(lam $y (app (lam $x (add (var $x) 0)) (var $y)))"),
+        term("(lam $z (var $z))"),
+    );
+    (eg, p)
+}
+
+#[test]
+fn export_checks() {
+    let (eg, p) = lambda_proof();
+    let exported = p.export(&eg);
+    assert_eq!(exported.check(), Ok(()));
+
+    let (l, r) = exported.equation();
+    assert_eq!(l.to_string(), eg.get_syn_expr(&p.l).unwrap().to_string());
+    assert_eq!(r.to_string(), eg.get_syn_expr(&p.r).unwrap().to_string());
+
+    let rules: Vec<&ProofRule> = exported.steps.iter().map(|s| &s.rule).collect();
+    assert!(rules.contains(&&ProofRule::Congruence));
+    assert!(rules.contains(&&ProofRule::Explicit(Some("beta".to_string()))));
+
+    // shared sub-proofs are only exported once.
+    let n = exported.steps.len();
+    let mut distinct: Vec<String> = exported.steps.iter().map(|s| format!("{s:?}")).collect();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), n);
+}
+
+#[test]
+fn export_detects_invalid_steps() {
+    let (eg, p) = lambda_proof();
+    let exported = p.export(&eg);
+
+    let i = exported
+        .steps
+        .iter()
+        .position(|s| s.rule == ProofRule::Transitivity)
+        .unwrap();
+    let mut broken = exported.clone();
+    broken.steps[i].rhs = term("(var $w)");
+    assert_eq!(broken.check().unwrap_err().step, i);
+
+    let mut broken = exported.clone();
+    broken.steps[i].children.reverse();
+    assert!(broken.check().is_err());
+}
+
+// a step whose children are renamed by `renaming`.
+fn step(
+    lhs: &str,
+    rhs: &str,
+    rule: ProofRule,
+    children: Vec<usize>,
+    renaming: &[(&str, &str)],
+) -> ProofStep<SymbolLang> {
+    let m: Vec<(Slot, Slot)> = renaming
+        .iter()
+        .map(|(x, y)| (Slot::named(x), Slot::named(y)))
+        .collect();
+    ProofStep {
+        lhs: term(lhs),
+        rhs: term(rhs),
+        rule,
+        renamings: children.iter().map(|_| SlotMap::from_pairs(&m)).collect(),
+        children,
+        subst: None,
+    }
+}
+
+#[test]
+fn export_rejects_captured_slots() {
+    // `$y` is free on the left, but bound on the right.
+    let forged = ExportedProof {
+        steps: vec![step(
+            "(lam $x (app (var $x) (var $y)))",
+            "(lam $y (app (var $y) (var $y)))",
+            ProofRule::Reflexivity,
+            vec![],
+            &[],
+        )],
+        root: 0,
+    };
+    assert!(forged.check().is_err());
+
+    // the same capture by a congruence step, whose children are fine on their own.
+    let forged = ExportedProof {
+        steps: vec![
+            step(
+                "(app (var $y) (var $y))",
+                "(app (var $y) (var $y))",
+                ProofRule::Reflexivity,
+                vec![],
+                &[],
+            ),
+            step(
+                "(lam $y (app (var $y) (var $y)))",
+                "(lam $x (app (var $x) (var $y)))",
+                ProofRule::Congruence,
+                vec![0],
+                &[("y", "y")],
+            ),
+        ],
+        root: 1,
+    };
+    assert!(forged.check().is_err());
+
+    // renaming the free slot `$b` of a child to `$a` must not let the binder `$a` capture it.
+    let child = step(
+        "(lam $a (f (var $a) (var $b)))",
+        "(k (var $b))",
+        ProofRule::Explicit(None),
+        vec![],
+        &[],
+    );
+    let forged = ExportedProof {
+        steps: vec![
+            child.clone(),
+            step(
+                "(k (var $a))",
+                "(lam $a (f (var $a) (var $a)))",
+                ProofRule::Symmetry,
+                vec![0],
+                &[("b", "a")],
+            ),
+        ],
+        root: 1,
+    };
+    assert!(forged.check().is_err());
+
+    let valid = ExportedProof {
+        steps: vec![
+            child,
+            step(
+                "(k (var $a))",
+                "(lam $c (f (var $c) (var $a)))",
+                ProofRule::Symmetry,
+                vec![0],
+                &[("b", "a")],
+            ),
+        ],
+        root: 1,
+    };
+    assert_eq!(valid.check(), Ok(()));
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn export_json_roundtrip() {
+    let (eg, p) = lambda_proof();
+    let exported = p.export(&eg);
+    let s = p.to_json(&eg);
+    let imported = ExportedProof::<SymbolLang>::from_json(&s).unwrap();
+    assert_eq!(imported, exported);
+
+    let root = &exported.steps[exported.root];
+    let broken = s.replacen(
+        &format!("\"rhs\": \"{}\"", root.rhs),
+        "\"rhs\": \"(var $w)\"",
+        1,
+    );
+    assert_ne!(broken, s);
+    assert!(matches!(
+        ExportedProof::<SymbolLang>::from_json(&broken),
+        Err(ProofJsonError::Check(_))
+    ));
+
+    assert!(matches!(
+        ExportedProof::<SymbolLang>::from_json("{}"),
+        Err(ProofJsonError::Format(_))
+    ));
+}
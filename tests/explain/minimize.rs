@@ -0,0 +1,86 @@
+use crate::*;
+
+fn minimize(eg: &EGraph<SymbolLang>, p: &ProvenEq, mode: ProofMinimization) -> MinimizedProof {
+    let m = eg.minimize_proof(p, mode);
+    assert_eq!(m.steps_before, p.step_count());
+    assert_eq!(m.steps_after, m.proof.step_count());
+    assert!(m.steps_after <= m.steps_before);
+    assert_eq!(m.proof.l.id, p.l.id);
+    assert_eq!(m.proof.r.id, p.r.id);
+    assert_eq!(m.proof.export(eg).check(), Ok(()));
+    m
+}
+
+const NAMES: [&str; 6] = ["a", "b", "c", "d", "e", "f"];
+
+fn constants() -> EGraph<SymbolLang> {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let ids: Vec<_> = NAMES.iter().map(|x| eg.add_syn_expr(term(x))).collect();
+    for (x, y) in [(0, 1), (2, 3), (4, 5), (1, 3), (5, 0)] {
+        let j = format!("{}{}", NAMES[x], NAMES[y]);
+        eg.union_justified(&ids[x], &ids[y], Some(j));
+    }
+    eg
+}
+
+#[test]
+fn minimize_removes_detours() {
+    let mut eg = constants();
+    let mut improved = false;
+    for x in NAMES {
+        for y in NAMES {
+            let p = eg.explain_equivalence(term(x), term(y));
+            let m = minimize(&eg, &p, ProofMinimization::Simplify);
+            improved |= m.steps_after < m.steps_before;
+        }
+    }
+    assert!(improved);
+
+    // `f = a` was unioned directly, but the union-find may take a detour.
+    let p = eg.explain_equivalence(term("a"), term("f"));
+    let m = minimize(&eg, &p, ProofMinimization::Simplify);
+    assert_eq!(m.steps_after, 2);
+}
+
+#[test]
+fn minimize_greedy_shortcuts() {
+    let mut eg = constants();
+    let c = eg.add_syn_expr(term("c"));
+    let e = eg.add_syn_expr(term("e"));
+    // already equal, but the proof is registered anyway.
+    eg.union_justified(&e, &c, Some("ec".to_string()));
+
+    let p = eg.explain_equivalence(term("c"), term("e"));
+    let simple = minimize(&eg, &p, ProofMinimization::Simplify);
+    let greedy = minimize(&eg, &p, ProofMinimization::GreedyShortest);
+    assert!(greedy.steps_after < simple.steps_after);
+    // symmetry of the explicit step.
+    assert_eq!(greedy.steps_after, 2);
+}
+
+#[test]
+fn minimize_keeps_rule_instances() {
+    let rules = [
+        Rewrite::new("add-comm", "(add ?a ?b)", "(add ?b ?a)"),
+        Rewrite::new("add-assoc", "(add ?a (add ?b ?c))", "(add (add ?a ?b) ?c)"),
+        Rewrite::new("mul-zero", "(mul ?a 0)", "0"),
+    ];
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = term("(add (var $x) (add (mul (var $y) 0) (var $z)))");
+    let b = term("(add (add (var $z) 0) (var $x))");
+    eg.add_syn_expr(a.clone());
+    eg.add_syn_expr(b.clone());
+    for _ in 0..4 {
+        apply_rewrites(&mut eg, &rules);
+    }
+    let p = eg.explain_equivalence(a.clone(), b.clone());
+
+    let checker = ProofChecker::from_rewrites(&rules);
+    for mode in [
+        ProofMinimization::Simplify,
+        ProofMinimization::GreedyShortest,
+    ] {
+        let m = minimize(&eg, &p, mode);
+        assert_eq!(checker.check(&m.proof.export(&eg), &a, &b), Ok(()));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::*;
+
+#[test]
+fn explain_ids() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_syn_expr(term("(f $x)"));
+    let b = eg.add_syn_expr(term("(g $x)"));
+    assert!(eg.explain_equivalence_ids(&a, &b).is_none());
+
+    eg.union_justified(&a, &b, Some("f-g".to_string()));
+    let p = eg.explain_equivalence_ids(&a, &b).unwrap();
+    let Equation { l, r } = p.equ();
+    assert_eq!((l.id, r.id), (a.id, b.id));
+    assert!(p.to_flat_string(&eg).contains("f-g"));
+}
+
+#[test]
+fn try_explain_terms() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_syn_expr(term("(f a)"));
+    let b = eg.add_syn_expr(term("(f b)"));
+
+    let res = eg.try_explain_equivalence(term("(f a)"), term("(f b)"));
+    assert_eq!(res.unwrap_err(), ExplainError::NotEquivalent(a.id, b.id));
+
+    let x = eg.add_syn_expr(term("a"));
+    let y = eg.add_syn_expr(term("b"));
+    eg.union(&x, &y);
+    assert!(eg
+        .try_explain_equivalence(term("(f a)"), term("(f b)"))
+        .is_ok());
+}
+
+#[test]
+fn explanations_disabled() {
+    let a = term("(mul (add (var $x) (var $y)) (add (var $x) (var $y)))");
+    let b = term(
+        "(add (mul (var $x) (var $x))
+         (add (mul (var $x) (var $y))
+         (add (mul (var $x) (var $y))
+              (mul (var $y) (var $y)))))",
+    );
+
+    let eg = EGraph::<Arith>::new().with_explanations(false);
+    assert!(!eg.explanations_enabled());
+    let mut runner = Runner::<Arith, (), ()>::new()
+        .with_egraph(eg)
+        .with_expr(&a)
+        .with_iter_limit(10);
+    runner.run(&get_all_rewrites());
+
+    let eg = &mut runner.egraph;
+    let i = eg.add_syn_expr(a.clone());
+    let j = eg.add_syn_expr(b.clone());
+    assert!(eg.eq(&i, &j));
+    assert!(eg
+        .ids()
+        .into_iter()
+        .all(|c| eg.eclass(c).syn_enode().is_none()));
+    assert!(eg.get_syn_node(&i).is_none());
+    assert!(eg.get_syn_expr(&i).is_none());
+    assert!(eg.explain_equivalence_ids(&i, &j).is_none());
+    assert!(eg.explain_redundancy(&i, Slot::named("x")).is_none());
+    assert!(eg.explain_symmetry(&i, &SlotMap::new()).is_none());
+    assert_eq!(
+        eg.try_explain_equivalence(a, b).unwrap_err(),
+        ExplainError::Disabled
+    );
+}
+
+// a runner hook that only explains the equivalence once it holds.
+#[test]
+fn explain_in_hook() {
+    let start = term("(add (mul x 1) 0)");
+    let rules = [
+        Rewrite::new("mul-one", "(mul ?a 1)", "?a"),
+        Rewrite::new("add-zero", "(add ?a 0)", "?a"),
+    ];
+    let mut runner = Runner::<SymbolLang, (), ()>::new();
+    let i = runner.egraph.add_syn_expr(start);
+    let goal = runner.egraph.add_syn_expr(term("x"));
+
+    let explained = std::rc::Rc::new(std::cell::Cell::new(false));
+    let explained2 = explained.clone();
+    let mut runner = runner.with_hook(move |r| {
+        if let Some(p) = r.egraph.explain_equivalence_ids(&i, &goal) {
+            assert!(p.to_flat_string(&r.egraph).contains("add-zero"));
+            explained2.set(true);
+        }
+        Ok(())
+    });
+    runner.run(&rules);
+    assert!(explained.get());
+}
+
+#[test]
+fn explain_redundant_slot() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_syn_expr(term("(mul (var $x) 0)"));
+    let zero = eg.add_syn_expr(term("0"));
+    let x = Slot::named("x");
+    assert!(eg.explain_redundancy(&a, x).is_none());
+
+    eg.union_justified(&a, &zero, Some("mul-zero".to_string()));
+    let p = eg.explain_redundancy(&a, x).unwrap();
+    let Equation { l, r } = p.equ();
+    assert_eq!((l.id, r.id), (a.id, a.id));
+    assert_eq!(l.slots().len(), 1);
+    assert!(l.slots().is_disjoint(&r.slots()));
+    assert!(p.to_string(&eg).contains("mul-zero"));
+
+    assert!(eg.explain_redundancy(&a, Slot::named("y")).is_none());
+}
+
+#[test]
+fn explain_symmetric_class() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_syn_expr(term("(add (var $x) (var $y))"));
+    let b = eg.add_syn_expr(term("(add (var $y) (var $x))"));
+    let (x, y) = (Slot::named("x"), Slot::named("y"));
+    let swap = SlotMap::from_pairs(&[(x, y), (y, x)]);
+    assert!(eg.explain_symmetry(&a, &swap).is_none());
+
+    eg.union_justified(&a, &b, Some("add-comm".to_string()));
+    let p = eg.explain_symmetry(&a, &swap).unwrap();
+    let Equation { l, r } = p.equ();
+    assert_eq!((l.id, r.id), (a.id, a.id));
+    // the arguments are swapped, up to renaming.
+    for (s, v) in l.m.iter() {
+        let w = r.m[s];
+        assert_ne!(v, w);
+        assert_eq!(r.m[l.m.inverse()[w]], v);
+    }
+    assert!(p.to_string(&eg).contains("add-comm"));
+
+    // not a permutation.
+    let m = SlotMap::from_pairs(&[(x, y)]);
+    assert!(eg.explain_symmetry(&a, &m).is_none());
+}
+
+mod checker;
+mod export;
+mod minimize;
+mod registry;
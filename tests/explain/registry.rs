@@ -0,0 +1,48 @@
+use crate::*;
+
+fn run(eg: EGraph<Arith>) -> Runner<Arith, (), ()> {
+    let start = term("(mul (add (var $x) (var $y)) (add (var $x) (var $y)))");
+    let mut runner = Runner::<Arith, (), ()>::new()
+        .with_egraph(eg)
+        .with_expr(&start)
+        .with_iter_limit(3);
+    runner.run(&get_all_rewrites());
+    runner
+}
+
+fn explain_all(eg: &mut EGraph<Arith>) {
+    let a = term("(mul (add (var $x) (var $y)) (add (var $x) (var $y)))");
+    let b = term("(mul (add (var $y) (var $x)) (add (var $x) (var $y)))");
+    let p = eg.explain_equivalence(a, b);
+    assert_eq!(p.export(eg).check(), Ok(()));
+}
+
+#[test]
+fn collect_proofs() {
+    let mut runner = run(EGraph::new());
+    let eg = &mut runner.egraph;
+
+    let before = eg.proof_registry_stats();
+    let dropped = eg.collect_proofs();
+    let after = eg.proof_registry_stats();
+    assert!(dropped > 0);
+    assert_eq!(after.equations + dropped, before.equations);
+    assert!(after.steps < before.steps);
+    assert!(after.approx_bytes < before.approx_bytes);
+
+    // collecting again finds nothing new.
+    assert_eq!(eg.collect_proofs(), 0);
+    explain_all(eg);
+}
+
+#[test]
+fn proof_registry_cap() {
+    let uncapped = run(EGraph::new()).egraph.proof_registry_stats();
+
+    let mut eg = EGraph::new();
+    eg.set_proof_registry_cap(Some(100));
+    let mut runner = run(eg);
+    let eg = &mut runner.egraph;
+    assert!(eg.proof_registry_stats().equations < uncapped.equations);
+    explain_all(eg);
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+#[test]
+fn constrained_node_filter() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(let a b)"));
+    let b = eg.add_expr(term("(g (h (i c)))"));
+    eg.union(&a, &b);
+    let c = eg.add_expr(term("(let c d)"));
+
+    let ex = Extractor::new_filtered(&eg, AstSize, |x: &SymbolLang| x.op.as_str() != "let");
+    assert_eq!(
+        ex.try_extract(&a, &eg).unwrap().to_string(),
+        "(g (h (i c)))"
+    );
+    assert_eq!(
+        ex.try_extract(&c, &eg),
+        Err(ExtractError::Unextractable(c.id))
+    );
+
+    let ex = Extractor::new(&eg, AstSize);
+    assert_eq!(ex.try_extract(&a, &eg).unwrap().to_string(), "(let a b)");
+}
+
+#[test]
+fn constrained_forbidden_slots() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(h $x $y)"));
+    let b = eg.add_expr(term("(g (k $x))"));
+    eg.union(&a, &b);
+
+    let ex = Extractor::new(&eg, AstSize);
+    let y: SmallHashSet<Slot> = [Slot::named("y")].into_iter().collect();
+    let t = ex.try_extract_avoiding(&a, &y, &eg).unwrap().to_string();
+    assert!(t.starts_with("(h $x "));
+    assert!(!t.contains("$y"));
+
+    let x: SmallHashSet<Slot> = [Slot::named("x")].into_iter().collect();
+    assert_eq!(
+        ex.try_extract_avoiding(&a, &x, &eg),
+        Err(ExtractError::ForbiddenSlot(Slot::named("x")))
+    );
+}
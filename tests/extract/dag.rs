@@ -0,0 +1,60 @@
+use crate::*;
+
+// (f (h D) (k D)) has tree cost 13 and DAG cost 8, while the alternative has tree and DAG cost 10.
+fn shared_egraph() -> (EGraph<SymbolLang>, AppliedId) {
+    let mut eg = EGraph::new();
+    let d = "(d1 (d2 (d3 (d4 x))))";
+    let a = eg.add_expr(term(&format!("(f (h {d}) (k {d}))")));
+    let b = eg.add_expr(term("(g (e1 (e2 (e3 (e4 (e5 (e6 (e7 (e8 x)))))))))"));
+    eg.union(&a, &b);
+    (eg, a)
+}
+
+#[test]
+fn dag_extract_sharing() {
+    let (eg, a) = shared_egraph();
+    let tree = Extractor::new(&eg, AstSize);
+    assert_eq!(tree.extract(&a, &eg).node.op.to_string(), "g");
+
+    let res = DagExtractor::new(&eg, AstSize).extract(&a, &eg);
+    assert_eq!(res.term.node.op.to_string(), "f");
+    assert_eq!(res.cost, 8);
+    assert!(res.optimal);
+    assert_eq!(res.lower_bound, 8);
+}
+
+#[test]
+fn dag_extract_step_limit() {
+    let (eg, a) = shared_egraph();
+    let res = DagExtractor::new(&eg, AstSize)
+        .with_step_limit(0)
+        .extract(&a, &eg);
+    assert_eq!(res.cost, 10);
+    assert!(!res.optimal);
+    assert!(res.lower_bound < res.cost);
+}
+
+#[test]
+fn dag_extract_renamings() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f (var $x) (var $y))"));
+    let res = DagExtractor::new(&eg, AstSize).extract(&a, &eg);
+    assert_eq!(res.cost, 3);
+
+    let b = eg.add_expr(term("(f (var $x) (var $x))"));
+    let res = DagExtractor::new(&eg, AstSize).extract(&b, &eg);
+    assert_eq!(res.cost, 2);
+}
+
+#[test]
+fn dag_try_extract_missing_class() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f a)"));
+    let ex = DagExtractor::new(&eg, AstSize);
+
+    // `b` is added after the extractor was created.
+    let b = eg.add_expr(term("(g b)"));
+
+    assert_eq!(ex.try_extract(&a, &eg).unwrap().cost, 2);
+    assert!(ex.try_extract(&b, &eg).is_none());
+}
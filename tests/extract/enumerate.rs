@@ -0,0 +1,106 @@
+use crate::*;
+
+#[test]
+fn enumerate_symbols() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(add a b)"));
+    let b = eg.add_expr(term("(add b a)"));
+    eg.union(&a, &b);
+
+    assert_eq!(
+        TermEnumerator::new(&eg).count_by_size(a.id, 4),
+        vec![0, 0, 0, 2, 0]
+    );
+    let terms: Vec<String> = eg.enumerate_terms(&a, 3).map(|t| t.to_string()).collect();
+    assert_eq!(terms, vec!["(add a b)", "(add b a)"]);
+}
+
+// the symmetry of the e-class makes it represent both argument orders, although it only stores one e-node.
+#[test]
+fn enumerate_symmetric_class() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(add (var $x) (var $y))"));
+    let b = eg.add_expr(term("(add (var $y) (var $x))"));
+    eg.union(&a, &b);
+    assert_eq!(eg.enodes(a.id).len(), 1);
+
+    assert_eq!(eg.count_terms(a.id, 10), 2);
+    let mut terms: Vec<String> = eg.enumerate_terms(&a, 10).map(|t| t.to_string()).collect();
+    terms.sort();
+    assert_eq!(
+        terms,
+        vec!["(add (var $x) (var $y))", "(add (var $y) (var $x))"]
+    );
+}
+
+// terms that only differ in the names of bound or redundant slots are counted once.
+#[test]
+fn enumerate_up_to_alpha() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(lam $x (var $x))"));
+    let b = eg.add_expr(term("(lam $y (var $y))"));
+    assert!(eg.eq(&a, &b));
+    assert_eq!(eg.count_terms(a.id, 10), 1);
+
+    let zero = eg.add_expr(term("zero"));
+    let sub = eg.add_expr(term("(sub (var $x) (var $x))"));
+    eg.union(&zero, &sub);
+    assert_eq!(eg.count_terms(zero.id, 10), 2);
+    assert_eq!(eg.enumerate_terms(&zero, 10).count(), 2);
+}
+
+#[test]
+fn enumerate_cyclic() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("a"));
+    let fa = eg.add_expr(term("(f a)"));
+    eg.union(&a, &fa);
+
+    assert_eq!(eg.count_terms(a.id, 5), 5);
+    let terms: Vec<String> = eg.enumerate_terms(&a, 3).map(|t| t.to_string()).collect();
+    assert_eq!(terms, vec!["a", "(f a)", "(f (f a))"]);
+
+    // counts saturate instead of overflowing.
+    let b = eg.add_expr(term("b"));
+    let gab = eg.add_expr(term("(g a b)"));
+    let fb = eg.add_expr(term("(f b)"));
+    eg.union(&b, &gab);
+    eg.union(&b, &fb);
+    assert_eq!(eg.count_terms(b.id, 200), u64::MAX);
+}
+
+// the enumerated terms are pairwise distinct, match the counts, and are represented by the e-class.
+#[test]
+fn enumerate_matches_count() {
+    let mut eg = EGraph::<Arith>::new();
+    let i = eg.add_expr(term("(mul (add (var $x) (var $y)) (add 2 3))"));
+    let rewrites = get_all_rewrites();
+    for _ in 0..2 {
+        apply_rewrites(&mut eg, &rewrites);
+    }
+
+    let n = 7;
+    let mut enumerator = TermEnumerator::new(&eg);
+    let counts = enumerator.count_by_size(i.id, n);
+    let terms: Vec<RecExpr<Arith>> = enumerator.into_terms(&i, n).collect();
+    assert_eq!(counts.iter().sum::<u64>(), terms.len() as u64);
+    assert!(terms.len() > 2);
+
+    let mut sizes: Vec<usize> = terms.iter().map(|t| AstSize.cost_rec(t) as usize).collect();
+    assert!(sizes.windows(2).all(|w| w[0] <= w[1]));
+    sizes.dedup();
+    for s in sizes {
+        let k = terms
+            .iter()
+            .filter(|t| AstSize.cost_rec(t) as usize == s)
+            .count();
+        assert_eq!(counts[s], k as u64);
+    }
+
+    let distinct: HashSet<String> = terms.iter().map(|t| t.to_string()).collect();
+    assert_eq!(distinct.len(), terms.len());
+    for t in terms {
+        let j = eg.add_expr(t);
+        assert!(eg.eq(&i, &j));
+    }
+}
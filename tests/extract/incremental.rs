@@ -0,0 +1,51 @@
+use crate::*;
+
+// checks that `ex` agrees with an extractor created from scratch.
+fn assert_fresh<CF: CostFunction<SymbolLang> + Default>(
+    ex: &Extractor<SymbolLang, CF>,
+    eg: &EGraph<SymbolLang>,
+) {
+    let fresh = Extractor::new(eg, CF::default());
+    for i in eg.ids() {
+        let i = AppliedId::new(i, SlotMap::identity(&eg.slots(i)));
+        assert_eq!(ex.try_best_cost(&i, eg), fresh.try_best_cost(&i, eg));
+    }
+}
+
+#[test]
+fn incremental_refresh() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f (g (h x)) (g (h x)))"));
+    let mut ex = Extractor::new(&eg, AstSize);
+    assert_eq!(ex.try_best_cost(&a, &eg), Some(7));
+
+    // makes `(h x)` cheaper, which propagates to `a`.
+    let hx = eg.add_expr(term("(h x)"));
+    let y = eg.add_expr(term("y"));
+    eg.union(&hx, &y);
+    let b = eg.add_expr(term("(k z)"));
+    ex.refresh(&eg);
+    assert_eq!(ex.try_best_cost(&a, &eg), Some(5));
+    assert_eq!(ex.try_best_cost(&b, &eg), Some(2));
+    assert_eq!(ex.extract(&a, &eg).to_string(), "(f (g y) (g y))");
+    assert_fresh(&ex, &eg);
+
+    eg.union(&a, &b);
+    ex.refresh(&eg);
+    assert_eq!(ex.extract(&a, &eg).to_string(), "(k z)");
+    assert_fresh(&ex, &eg);
+}
+
+#[test]
+fn incremental_redundant_slots() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    eg.add_expr(term("(f (g $x $y) (var $y))"));
+    let mut ex = Extractor::new(&eg, AstSize);
+
+    // `$y` becomes redundant in `(g $x $y)`.
+    let g = eg.add_expr(term("(g $x $y)"));
+    let g2 = eg.add_expr(term("(g $x $z)"));
+    eg.union(&g, &g2);
+    ex.refresh(&eg);
+    assert_fresh(&ex, &eg);
+}
@@ -0,0 +1,18 @@
+mod dag;
+
+mod top_k;
+
+mod slot_cost;
+
+mod try_extract;
+
+mod incremental;
+
+mod constrained;
+
+mod sample;
+
+mod enumerate;
+
+#[cfg(feature = "explanations")]
+mod proven;
@@ -0,0 +1,41 @@
+use crate::*;
+
+#[test]
+fn extract_proven_beta() {
+    let start = term("(app (lam $x (add (var $x) 0)) (f $y))");
+    let rules = [
+        Rewrite::new("beta", "(app (lam $x ?b) ?t)", "?b[(var $x) := ?t]"),
+        Rewrite::new("add-zero", "(add ?a 0)", "?a"),
+    ];
+
+    let mut eg = EGraph::<SymbolLang>::new();
+    let i = eg.add_syn_expr(start.clone());
+    for _ in 0..3 {
+        apply_rewrites(&mut eg, &rules);
+    }
+
+    let res = ast_size_extract_proven(&i, &mut eg);
+    assert_eq!(res.term.to_string(), "(f $y)");
+
+    // the proof talks about the syntactic e-classes of both terms, up to renaming of slots.
+    let Equation { l, r } = res.proof.equ();
+    assert_eq!(l.id, i.id);
+    assert_eq!(r.id, eg.add_syn_expr(res.term.clone()).id);
+
+    let flat = res.proof.to_flat_string(&eg);
+    let lines: Vec<&str> = flat.lines().collect();
+    assert!(lines.len() > 1);
+    assert!(lines.iter().any(|x| x.contains("beta")), "{flat}");
+    assert!(lines.last().unwrap().contains("(f $"), "{flat}");
+}
+
+// the extracted term is already the original term.
+#[test]
+fn extract_proven_refl() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let i = eg.add_syn_expr(term("(g a b)"));
+    let extractor = Extractor::new(&eg, AstSize);
+    let res = extractor.extract_proven(&i, &mut eg);
+    assert_eq!(res.term.to_string(), "(g a b)");
+    assert_eq!(res.proof.to_flat_string(&eg), "(g a b)");
+}
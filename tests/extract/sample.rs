@@ -0,0 +1,84 @@
+use crate::*;
+
+fn eval(re: &RecExpr<Arith>, env: &impl Fn(Slot) -> u32) -> u32 {
+    let c = |i: usize| eval(&re.children[i], env);
+    match &re.node {
+        Arith::Add(..) => c(0) + c(1),
+        Arith::Mul(..) => c(0) * c(1),
+        Arith::Number(n) => *n,
+        Arith::Var(s) => env(*s),
+        _ => panic!("unexpected node {:?}", re.node),
+    }
+}
+
+fn depth(re: &RecExpr<Arith>) -> usize {
+    1 + re.children.iter().map(depth).max().unwrap_or(0)
+}
+
+// samples terms from a saturated e-graph, and checks that they all evaluate to the same value.
+#[test]
+fn sample_fuzz_rewrites() {
+    let mut eg = EGraph::<Arith>::new();
+    let orig = term("(mul (add (var $x) (var $y)) (add 2 3))");
+    let i = eg.add_expr(orig.clone());
+    let rewrites = get_all_rewrites();
+    for _ in 0..3 {
+        apply_rewrites(&mut eg, &rewrites);
+    }
+
+    let env = |s: Slot| if s == Slot::named("x") { 3 } else { 5 };
+    let expected = eval(&orig, &env);
+
+    let sample = |seed| {
+        let mut sampler = Sampler::new(&eg, seed).with_max_depth(6);
+        (0..30)
+            .map(|_| sampler.sample(&i).unwrap())
+            .collect::<Vec<_>>()
+    };
+    let terms = sample(42);
+    assert_eq!(terms, sample(42));
+
+    let distinct: HashSet<String> = terms.iter().map(|t| t.to_string()).collect();
+    assert!(distinct.len() > 1);
+
+    for t in &terms {
+        assert!(depth(t) <= 6);
+        assert_eq!(eval(t, &env), expected, "{t}");
+    }
+    for t in terms {
+        let j = eg.add_expr(t);
+        assert!(eg.eq(&i, &j));
+    }
+}
+
+#[test]
+fn sample_weights_and_depth() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f (lam $x (var $x)))"));
+    let b = eg.add_expr(term("(g (h (i (j k))))"));
+    eg.union(&a, &b);
+
+    let mut sampler =
+        Sampler::new(&eg, 0).with_weights(
+            |x: &SymbolLang| {
+                if x.op.as_str() == "g" {
+                    0.0
+                } else {
+                    1.0
+                }
+            },
+        );
+    for _ in 0..10 {
+        assert_eq!(sampler.sample(&a).unwrap().node.op.as_str(), "f");
+    }
+
+    // `(g ...)` has depth 5, so only `(f ...)` fits.
+    let mut sampler = Sampler::new(&eg, 0).with_max_depth(3);
+    for _ in 0..10 {
+        let t = sampler.sample(&a).unwrap();
+        // the bound slot gets a fresh name.
+        assert!(!t.to_string().contains("$x"));
+    }
+    let mut sampler = Sampler::new(&eg, 0).with_max_depth(2);
+    assert!(sampler.sample(&a).is_none());
+}
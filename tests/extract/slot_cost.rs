@@ -0,0 +1,140 @@
+use crate::*;
+
+// AstSize, but every free variable captured by a `lam` costs 10.
+struct NoCapture;
+
+impl SlotCostFunction<SymbolLang> for NoCapture {
+    type Cost = u64;
+
+    fn slot_cost<C>(&self, enode: &SymbolLang, slots: &SmallHashSet<Slot>, costs: C) -> u64
+    where
+        C: Fn(&AppliedId) -> u64,
+    {
+        let mut c = 1;
+        for child in enode.applied_id_occurrences() {
+            c += costs(child);
+        }
+        if enode.op.as_str() == "lam" {
+            c += 10 * slots.len() as u64;
+        }
+        c
+    }
+}
+
+#[test]
+fn slot_cost_penalizes_captures() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(lam $y (f (var $y) (var $x)))"));
+    let b = eg.add_expr(term("(g (var $x) (h (i (j (k a)))))"));
+    eg.union(&a, &b);
+
+    let ast = Extractor::new(&eg, AstSize);
+    assert_eq!(ast.extract(&a, &eg).node.op.as_str(), "lam");
+
+    let ex = Extractor::new(&eg, NoCapture);
+    assert_eq!(
+        ex.extract(&a, &eg).to_string(),
+        "(g (var $x) (h (i (j (k a)))))"
+    );
+    assert_eq!(ex.get_best_cost::<()>(&eg.find_applied_id(&a)), 7);
+
+    // closed lambdas are not penalized.
+    let c = eg.add_expr(term("(lam $y (var $y))"));
+    let ex = Extractor::new(&eg, NoCapture);
+    assert_eq!(ex.extract(&c, &eg).node.op.as_str(), "lam");
+}
+
+// AstSize, but a `g`, `h` or `f` node costs 10 more if the slots passed to its children are not in ascending order.
+// So the best term of an e-class depends on how its slots are renamed.
+struct Sorted;
+
+impl SlotCostFunction<SymbolLang> for Sorted {
+    type Cost = u64;
+
+    fn slot_cost<C>(&self, enode: &SymbolLang, _slots: &SmallHashSet<Slot>, costs: C) -> u64
+    where
+        C: Fn(&AppliedId) -> u64,
+    {
+        let mut c = 1;
+        for child in enode.applied_id_occurrences() {
+            c += costs(child);
+        }
+        if ["g", "h", "f"].contains(&enode.op.as_str()) {
+            let names: Vec<String> = enode
+                .all_slot_occurrences()
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            if !names.is_sorted() {
+                c += 10;
+            }
+        }
+        c
+    }
+}
+
+#[test]
+fn slot_cost_depends_on_renaming() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(g (var $a) (var $b))"));
+    let b = eg.add_expr(term("(h (var $b) (var $a))"));
+    eg.union(&a, &b);
+
+    // both use the e-class of `a`, but with swapped slots.
+    let root = eg.add_expr(term(
+        "(pair (p (g (var $x) (var $y))) (q (g (var $y) (var $x))))",
+    ));
+
+    let ex = Extractor::new(&eg, Sorted);
+    assert_eq!(
+        ex.extract(&root, &eg).to_string(),
+        "(pair (p (g (var $x) (var $y))) (q (h (var $x) (var $y))))"
+    );
+    assert_eq!(ex.try_best_cost(&root, &eg), Some(9));
+
+    // with a symmetry, both renamings give the same terms.
+    let c = eg.add_expr(term("(f (var $a) (var $b))"));
+    let d = eg.add_expr(term("(f (var $b) (var $a))"));
+    eg.union(&c, &d);
+    let root = eg.add_expr(term("(q (f (var $y) (var $x)))"));
+
+    let ex = Extractor::new(&eg, Sorted);
+    assert_eq!(
+        ex.extract(&root, &eg).to_string(),
+        "(q (f (var $x) (var $y)))"
+    );
+}
+
+#[test]
+fn slot_cost_best_cost_respects_renaming() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(g (var $x) (var $y))"));
+    let b = eg.add_expr(term("(g (var $y) (var $x))"));
+
+    let ex = Extractor::new(&eg, Sorted);
+    assert_eq!(ex.get_best_cost::<()>(&eg.find_applied_id(&a)), 3);
+    assert_eq!(ex.get_best_cost::<()>(&eg.find_applied_id(&b)), 13);
+    assert_eq!(ex.try_best_cost(&b, &eg), Some(13));
+}
+
+#[test]
+fn slot_cost_refresh() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(g (var $a) (var $b))"));
+    let root = eg.add_expr(term("(q (g (var $y) (var $x)))"));
+
+    let mut ex = Extractor::new(&eg, Sorted);
+    assert_eq!(ex.try_best_cost(&root, &eg), Some(14));
+
+    let b = eg.add_expr(term("(h (var $b) (var $a))"));
+    eg.union(&a, &b);
+    ex.refresh(&eg);
+    assert_eq!(
+        ex.extract(&root, &eg).to_string(),
+        "(q (h (var $x) (var $y)))"
+    );
+    assert_eq!(ex.try_best_cost(&root, &eg), Some(4));
+
+    let fresh = Extractor::new(&eg, Sorted);
+    assert_eq!(ex.extract(&root, &eg), fresh.extract(&root, &eg));
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+#[test]
+fn top_k_ranked() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f a)"));
+    let b = eg.add_expr(term("(g b c)"));
+    let c = eg.add_expr(term("(h (i (j a)))"));
+    eg.union(&a, &b);
+    eg.union(&a, &c);
+
+    let res = TopKExtractor::new(&eg, AstSize, 5).extract(&a, &eg);
+    let res: Vec<(String, u64)> = res.into_iter().map(|(t, c)| (t.to_string(), c)).collect();
+    assert_eq!(
+        res,
+        vec![
+            ("(f a)".to_string(), 2),
+            ("(g b c)".to_string(), 3),
+            ("(h (i (j a)))".to_string(), 4),
+        ]
+    );
+
+    let res = TopKExtractor::new(&eg, AstSize, 2).extract(&a, &eg);
+    assert_eq!(res.len(), 2);
+}
+
+#[test]
+fn top_k_cyclic_binders() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(lam $x (var $x))"));
+    let b = eg.add_expr(term("(app (lam $y (var $y)) (lam $z (var $z)))"));
+    eg.union(&a, &b);
+
+    let res = TopKExtractor::new(&eg, AstSize, 3).extract(&a, &eg);
+    let costs: Vec<u64> = res.iter().map(|(_, c)| *c).collect();
+    assert_eq!(costs, vec![2, 5, 8]);
+    for (t, c) in &res {
+        assert_eq!(AstSize.cost_rec(t), *c);
+    }
+}
+
+// the same candidate is used inside of itself, its binders must not capture the slots around it.
+#[test]
+fn top_k_no_capture() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(var $c)"));
+    let b = eg.add_expr(term("(lam $b (app (var $c) (var $b)))"));
+    eg.union(&a, &b);
+
+    let res = TopKExtractor::new(&eg, AstSize, 5).extract(&a, &eg);
+    assert_eq!(res.len(), 5);
+    for (t, _) in res {
+        let i = eg.add_expr(t.clone());
+        assert!(eg.eq(&i, &a), "{t} is not in the e-class");
+    }
+}
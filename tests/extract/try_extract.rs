@@ -0,0 +1,25 @@
+use crate::*;
+
+#[test]
+fn try_extract_missing_class() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(f a)"));
+    let ex = Extractor::new(&eg, AstSize);
+
+    // `b` is added after the extractor was created.
+    let b = eg.add_expr(term("(g b)"));
+
+    assert_eq!(ex.try_extract(&a, &eg).unwrap().to_string(), "(f a)");
+    assert_eq!(ex.try_best_cost(&a, &eg), Some(2));
+
+    assert_eq!(
+        ex.try_extract(&b, &eg),
+        Err(ExtractError::Unextractable(b.id))
+    );
+    assert_eq!(ex.try_best_cost(&b, &eg), None);
+
+    assert_eq!(
+        ex.unextractable(&[a.clone(), b.clone(), b.clone()], &eg),
+        vec![b.id]
+    );
+}
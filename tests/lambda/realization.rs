@@ -92,6 +92,31 @@ pub fn check_eq<R: Realization>(s1: &str, s2: &str) {
     panic!("equality could not be found!");
 }
 
+// checks that an extractor that is refreshed after each step agrees with one created from scratch.
+pub fn check_refresh<R: Realization>(s: &str) {
+    let re = RecExpr::parse(s).unwrap();
+    let mut eg = EGraph::new();
+    eg.add_syn_expr(re);
+    let mut extractor = Extractor::new(&eg, AstSizeNoLet);
+    for _ in 0..10 {
+        R::step(&mut eg);
+        extractor.refresh(&eg);
+
+        let fresh = Extractor::new(&eg, AstSizeNoLet);
+        for i in eg.ids() {
+            let i = AppliedId::new(i, SlotMap::identity(&eg.slots(i)));
+            assert_eq!(
+                extractor.try_best_cost(&i, &eg),
+                fresh.try_best_cost(&i, &eg)
+            );
+        }
+
+        if eg.total_number_of_nodes() > NO_ENODES {
+            break;
+        }
+    }
+}
+
 // Non-Realization functions:
 
 fn extract_ast(eg: &EGraph<Lambda>, i: &AppliedId) -> RecExpr<Lambda> {
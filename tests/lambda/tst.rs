@@ -100,6 +100,14 @@ macro_rules! unpack_tests {
             check_simplify_to_nf::<$R>(p);
         }
 
+        #[test]
+        fn refresh_per_step() {
+            use lambda::*;
+
+            let p = "(app (lam $0 (lam $1 (app (var $0) (var $1)))) (lam $2 (var $2)))";
+            check_refresh::<$R>(p);
+        }
+
         #[test]
         fn inf_loop() {
             use lambda::*;
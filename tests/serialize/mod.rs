@@ -0,0 +1,97 @@
+use crate::*;
+
+#[test]
+fn serialize_roundtrip() {
+    let mut eg = EGraph::<Arith>::new();
+    let a = eg.add_expr(term("(add 1 2)"));
+    let b = eg.add_expr(term("(add 2 1)"));
+    eg.union(&a, &b);
+
+    // creates a cycle: `1 = (mul 1 1)`.
+    let one = eg.add_expr(term("1"));
+    let mul = eg.add_expr(term("(mul 1 1)"));
+    eg.union(&one, &mul);
+
+    let json = eg.to_serialized_json(&AstSize, &[a.id]);
+    assert!(json.contains("\"root_eclasses\""));
+    assert!(json.contains("\"op\": \"add\""));
+    assert!(json.contains("\"cost\": 1.0"));
+
+    let (mut eg2, roots) = EGraph::<Arith>::from_serialized_json(&json).unwrap();
+    assert_eq!(roots.len(), 1);
+    let a2 = eg2.add_expr(term("(add 2 (mul 1 1))"));
+    assert!(eg2.eq(&roots[0], &a2));
+    assert_eq!(eg2.ids().len(), eg.ids().len());
+}
+
+#[test]
+fn serialize_slots() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(lam $x (var $x))"));
+    let json = eg.to_serialized_json(&AstSize, &[a.id]);
+    assert!(json.contains("\"slot_args\""));
+    assert!(json.contains("\"child_slotmaps\""));
+
+    let res = EGraph::<SymbolLang>::from_serialized_json(&json);
+    assert!(matches!(res, Err(SerializeError::HasSlots(_))));
+}
+
+#[test]
+fn serialize_canonical_slots() {
+    let build = || {
+        let mut eg = EGraph::<SymbolLang>::new();
+        let a = eg.add_expr(term("(f (lam $x (app (var $x) (var $y))) (var $z))"));
+        (eg.to_serialized_json(&AstSize, &[a.id]), eg)
+    };
+    let (json, eg) = build();
+    for _ in 0..5 {
+        Slot::fresh();
+    }
+    assert_eq!(json, build().0);
+    assert!(json.contains("\"$0\""));
+    assert!(!json.contains("$f"));
+
+    // every child points to an exported node.
+    let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let nodes = v["nodes"].as_object().unwrap();
+    for n in nodes.values() {
+        for c in n["children"].as_array().unwrap() {
+            assert!(nodes.contains_key(c.as_str().unwrap()));
+        }
+    }
+    assert_eq!(nodes.len(), eg.total_number_of_nodes());
+}
+
+// in a single term, the costs of the nodes add up to the cost of the term.
+#[test]
+fn serialize_costs() {
+    // a `mul` costs as much as both of its children together.
+    struct Doubling;
+
+    impl CostFunction<Arith> for Doubling {
+        type Cost = u64;
+        fn cost<C>(&self, enode: &Arith, costs: C) -> u64
+        where
+            C: Fn(Id) -> u64,
+        {
+            let children: u64 = enode.ids().into_iter().map(costs).sum();
+            match enode {
+                Arith::Mul(..) => 2 * children,
+                _ => children + 1,
+            }
+        }
+    }
+
+    let mut eg = EGraph::<Arith>::new();
+    let re = term("(mul (add 1 2) 3)");
+    let a = eg.add_expr(re.clone());
+    let json = eg.to_serialized_json(&Doubling, &[a.id]);
+    let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let total: f64 = v["nodes"]
+        .as_object()
+        .unwrap()
+        .values()
+        .map(|n| n["cost"].as_f64().unwrap())
+        .sum();
+    assert_eq!(total, Doubling.cost_rec(&re) as f64);
+}
@@ -0,0 +1,33 @@
+use crate::*;
+
+#[test]
+fn symbol_lang_roundtrip() {
+    let s = "(let $x (app (lam $y (var $y)) (var $x)) (f $z $w))";
+    let re = RecExpr::<SymbolLang>::parse(s).unwrap();
+    assert_eq!(re.to_string(), s);
+}
+
+#[test]
+fn symbol_lang_binders() {
+    let mut eg = EGraph::<SymbolLang>::new();
+    let a = eg.add_expr(term("(lam $x (var $x))"));
+    let b = eg.add_expr(term("(lam $y (var $y))"));
+    assert!(eg.eq(&a, &b));
+    assert!(eg.slots(a.id).is_empty());
+
+    let c = eg.add_expr(term("(f $x $y)"));
+    assert_eq!(eg.slots(c.id).len(), 2);
+}
+
+#[test]
+fn symbol_lang_beta() {
+    let start = term("(app (lam $x (add (var $x) 0)) 3)");
+    let rules = [
+        Rewrite::new("beta", "(app (lam $x ?b) ?t)", "?b[(var $x) := ?t]"),
+        Rewrite::new("add-zero", "(add ?a 0)", "?a"),
+    ];
+    let mut runner = Runner::<SymbolLang, (), ()>::new().with_expr(&start);
+    runner.run(&rules);
+    let out = ast_size_extract(&runner.roots[0], &runner.egraph);
+    assert_eq!(out.to_string(), "3");
+}